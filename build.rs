@@ -0,0 +1,180 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use std::{
+    collections::HashMap,
+    env, fs,
+    path::{Path, PathBuf},
+};
+
+#[path = "src/layout.rs"]
+#[allow(dead_code)]
+mod layout;
+
+/// Returns the name of the type implementing `Solution` in a day's source, if any.
+fn find_solution_type(source: &str) -> Result<Option<String>, syn::Error> {
+    let file = syn::parse_file(source)?;
+
+    Ok(file
+        .items
+        .iter()
+        .find_map(|item| match item {
+            syn::Item::Impl(item_impl) => {
+                let (_, trait_path, _) = item_impl.trait_.as_ref()?;
+                if trait_path
+                    .segments
+                    .last()?
+                    .ident
+                    != "Solution"
+                {
+                    return None;
+                }
+                match item_impl.self_ty.as_ref() {
+                    syn::Type::Path(type_path) => Some(
+                        type_path
+                            .path
+                            .segments
+                            .last()?
+                            .ident
+                            .to_string(),
+                    ),
+                    _ => None,
+                }
+            }
+            _ => None,
+        }))
+}
+
+/// The examples directory, `src/examples` unless `aoc.toml` moves it.
+fn examples_dir(manifest_dir: &Path) -> PathBuf {
+    let config_path = manifest_dir.join("aoc.toml");
+    println!("cargo:rerun-if-changed={}", config_path.display());
+
+    let configured = fs::read_to_string(&config_path)
+        .ok()
+        .and_then(|contents| {
+            contents
+                .parse::<toml::Value>()
+                .ok()
+        })
+        .and_then(|config| {
+            Some(PathBuf::from(
+                config
+                    .get("paths")?
+                    .get("examples")?
+                    .as_str()?,
+            ))
+        })
+        .filter(|path| !path.as_os_str().is_empty());
+    layout::folder_dir(manifest_dir, "examples", configured.as_deref())
+}
+
+fn main() {
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let bin_dir = Path::new(&manifest_dir)
+        .join("src")
+        .join("bin");
+    let examples_dir = examples_dir(Path::new(&manifest_dir));
+    println!("cargo:rerun-if-changed={}", bin_dir.display());
+    println!("cargo:rerun-if-changed={}", examples_dir.display());
+
+    let out_dir = env::var("OUT_DIR").unwrap();
+    let example_tests_dir = Path::new(&out_dir).join("example_tests");
+    fs::create_dir_all(&example_tests_dir).unwrap();
+
+    // Days that rustc rejects, see `layout::COMPILE_ERRORS_VAR`.
+    println!("cargo:rerun-if-env-changed={}", layout::COMPILE_ERRORS_VAR);
+    let rejected = env::var(layout::COMPILE_ERRORS_VAR).unwrap_or_default();
+    let rejected: HashMap<&str, &str> = rejected
+        .lines()
+        .filter_map(|line| line.split_once('\t'))
+        .collect();
+
+    let mut modules = String::new();
+    let mut entries = String::new();
+    let mut compile_errors = String::new();
+
+    // Days of the default year are `NN.rs`, those of other years `YYYY-NN.rs`.
+    let mut days: Vec<(Option<i32>, u8, PathBuf)> = fs::read_dir(&bin_dir)
+        .map(|entries| {
+            entries
+                .filter_map(|entry| entry.ok())
+                .filter_map(|entry| {
+                    let stem = entry
+                        .file_name()
+                        .to_str()?
+                        .strip_suffix(".rs")?
+                        .to_string();
+                    let (year, day) = layout::parse_bin_name(&stem)?;
+                    Some((year, day, entry.path()))
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    days.sort();
+
+    for (year, day, path) in days {
+        println!("cargo:rerun-if-changed={}", path.display());
+        let (name, examples_dir, year_arg) = match year {
+            Some(year) => (
+                format!("{year}-{day:02}"),
+                examples_dir.join(year.to_string()),
+                format!("Some({year})"),
+            ),
+            None => (
+                format!("{day:02}"),
+                examples_dir.clone(),
+                "None".to_string(),
+            ),
+        };
+        println!("cargo:rerun-if-changed={}", examples_dir.display());
+        for example in layout::example_files(&examples_dir, day) {
+            println!("cargo:rerun-if-changed={}", example.display());
+        }
+
+        fs::write(
+            example_tests_dir.join(format!("{name}.rs")),
+            layout::example_tests(Path::new(&manifest_dir), &examples_dir, year, day),
+        )
+        .unwrap();
+
+        let source = fs::read_to_string(&path).unwrap();
+
+        // A day that does not compile would break the build of every binary including the
+        // registry. Leave it out and let the runner report it instead.
+        let solution = match (rejected.get(name.as_str()), find_solution_type(&source)) {
+            (None, Ok(Some(solution))) => solution,
+            (rejected, result) => {
+                let error = match (rejected, result) {
+                    (Some(error), _) => error.to_string(),
+                    (None, Err(e)) => format!("src/bin/{name}.rs: {e}"),
+                    (None, _) => format!("src/bin/{name}.rs: no type implements `Solution`"),
+                };
+                println!("cargo:warning={error}");
+                compile_errors.push_str(&format!(
+                    "        ({year_arg}, {day}, {error:?}.to_string()),\n"
+                ));
+                continue;
+            }
+        };
+
+        let module = format!("day{}", name.replace('-', "_"));
+        modules.push_str(&format!(
+            "#[path = {:?}]\n#[allow(dead_code, unused_attributes)]\nmod {module};\n",
+            path.display().to_string()
+        ));
+        let in_year = year
+            .map(|year| format!(".in_year({year})"))
+            .unwrap_or_default();
+        entries.push_str(&format!(
+            "        advent_of_code::Day::new::<{module}::{solution}>(){in_year},\n"
+        ));
+    }
+
+    let registry = format!(
+        "{modules}\npub fn registry() -> advent_of_code::Registry {{\n    advent_of_code::Registry::new(vec![\n{entries}    ])\n    .with_compile_errors(vec![\n{compile_errors}    ])\n}}\n"
+    );
+
+    fs::write(Path::new(&out_dir).join("days.rs"), registry).unwrap();
+}
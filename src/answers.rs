@@ -0,0 +1,187 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use crate::{input, PartResult};
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+/// How a part's answer compares to the accepted answer in `src/answers`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum Verdict {
+    /// The answer matches the accepted answer.
+    Correct,
+    /// The answer differs from, or did not produce, the accepted answer given here.
+    Wrong(String),
+    /// No answer has been accepted for this part yet.
+    #[default]
+    Unknown,
+}
+
+impl Verdict {
+    /// A stable, machine-readable name for the verdict.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Verdict::Correct => "correct",
+            Verdict::Wrong(_) => "wrong",
+            Verdict::Unknown => "unknown",
+        }
+    }
+
+    pub fn expected(&self) -> Option<&str> {
+        match self {
+            Verdict::Wrong(expected) => Some(expected),
+            _ => None,
+        }
+    }
+}
+
+/// The accepted answers of a day, as stored in `src/answers/NN.toml`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Answers {
+    pub part_one: Option<String>,
+    pub part_two: Option<String>,
+}
+
+const KEYS: [&str; 2] = ["part_one", "part_two"];
+
+impl Answers {
+    /// Parses an answers file. Answers may be given as strings or as integers.
+    pub fn parse(contents: &str) -> Result<Self, String> {
+        let table: toml::value::Table = toml::from_str(contents).map_err(|e| e.to_string())?;
+
+        if let Some(key) = table
+            .keys()
+            .find(|key| !KEYS.contains(&key.as_str()))
+        {
+            return Err(format!(
+                "unexpected key \"{key}\", expected one of: part_one, part_two"
+            ));
+        }
+
+        let answer = |key: &str| match table.get(key) {
+            None => Ok(None),
+            Some(toml::Value::String(answer)) => Ok(Some(answer.clone())),
+            Some(toml::Value::Integer(answer)) => Ok(Some(answer.to_string())),
+            Some(other) => Err(format!(
+                "{key} needs to be a string or an integer, got {}",
+                other.type_str()
+            )),
+        };
+
+        Ok(Self {
+            part_one: answer("part_one")?,
+            part_two: answer("part_two")?,
+        })
+    }
+
+    pub fn to_toml(&self) -> String {
+        let mut table = toml::value::Table::new();
+        for (key, answer) in KEYS
+            .iter()
+            .zip([&self.part_one, &self.part_two])
+        {
+            if let Some(answer) = answer {
+                table.insert(key.to_string(), toml::Value::String(answer.clone()));
+            }
+        }
+        toml::to_string(&table).unwrap()
+    }
+
+    pub fn get(&self, part: u8) -> Option<&str> {
+        match part {
+            1 => self.part_one.as_deref(),
+            2 => self.part_two.as_deref(),
+            _ => None,
+        }
+    }
+
+    pub fn verdict(&self, part: u8, answer: Option<&str>) -> Verdict {
+        match (self.get(part), answer) {
+            (None, _) => Verdict::Unknown,
+            (Some(expected), Some(answer)) if expected == answer => Verdict::Correct,
+            (Some(expected), _) => Verdict::Wrong(expected.to_string()),
+        }
+    }
+
+    /// Replaces the accepted answers with those of the solved parts in `results`.
+    pub fn accept(&mut self, results: &[PartResult]) {
+        for result in results {
+            if let Some(answer) = &result.answer {
+                match result.part {
+                    1 => self.part_one = Some(answer.clone()),
+                    2 => self.part_two = Some(answer.clone()),
+                    _ => {}
+                }
+            }
+        }
+    }
+}
+
+pub fn answers_path(day: u8) -> PathBuf {
+    input::folder_dir("answers").join(format!("{day:02}.toml"))
+}
+
+/// Reads the accepted answers for `day`. A missing file means no answers were accepted yet.
+pub fn load(day: u8) -> Result<Answers, String> {
+    let path = answers_path(day);
+    match fs::read_to_string(&path) {
+        Ok(contents) => Answers::parse(&contents).map_err(|e| format!("{}: {e}", path.display())),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Answers::default()),
+        Err(e) => Err(format!("{}: {e}", path.display())),
+    }
+}
+
+/// Writes the accepted answers for `day` and returns the path they were written to.
+pub fn save(day: u8, answers: &Answers) -> io::Result<PathBuf> {
+    let path = answers_path(day);
+    input::write_file(&path, &answers.to_toml())?;
+    Ok(path)
+}
+
+/// Sets the verdict of each result according to the accepted answers for `day`.
+pub fn verify(day: u8, results: &mut [PartResult]) {
+    let answers = load(day).unwrap_or_else(|e| {
+        eprintln!("Could not read accepted answers: {e}");
+        Answers::default()
+    });
+    for result in results {
+        result.verdict = Some(answers.verdict(result.part, result.answer.as_deref()));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_answers() {
+        let answers = Answers::parse("part_one = 24000\npart_two = \"##..\\n..##\"\n").unwrap();
+        assert_eq!(answers.get(1), Some("24000"));
+        assert_eq!(answers.get(2), Some("##..\n..##"));
+        assert_eq!(Answers::parse(&answers.to_toml()), Ok(answers));
+
+        assert_eq!(Answers::parse(""), Ok(Answers::default()));
+        assert!(Answers::parse("part_three = 1").is_err());
+        assert!(Answers::parse("part_one = 1.5").is_err());
+    }
+
+    #[test]
+    fn test_verdict() {
+        let answers = Answers {
+            part_one: Some("24000".to_string()),
+            part_two: None,
+        };
+        assert_eq!(answers.verdict(1, Some("24000")), Verdict::Correct);
+        assert_eq!(
+            answers.verdict(1, Some("23999")),
+            Verdict::Wrong("24000".to_string())
+        );
+        assert_eq!(
+            answers.verdict(1, None),
+            Verdict::Wrong("24000".to_string())
+        );
+        assert_eq!(answers.verdict(2, Some("45000")), Verdict::Unknown);
+    }
+}
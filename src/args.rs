@@ -0,0 +1,246 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use crate::bench::{parse_seconds, BenchOptions, Budget};
+use crate::client::{self, Client, ClientError};
+use crate::config;
+use crate::history::CompareOptions;
+use crate::input::{self, InputSource};
+use crate::output::Format;
+use crate::trace::{Filter, TraceOptions};
+use crate::worker::Limits;
+use std::path::PathBuf;
+
+/// Arguments shared by `cargo solve` and `cargo all`.
+#[derive(Debug, Default)]
+pub struct RunArgs {
+    pub format: Format,
+    /// Set when `--bench` was passed.
+    pub bench: Option<BenchOptions>,
+    /// Set when `--compare` was passed.
+    pub compare: Option<CompareOptions>,
+    /// Set when `--accept` was passed.
+    pub accept: bool,
+    /// Set by `--example`, `--input <path>` or `-` for stdin.
+    pub input: InputSource,
+    /// Set when `--timeout`, `--day-timeout` or `--memory-limit` was passed. Those not passed are
+    /// taken from [`Limits::configured`].
+    pub limits: Option<Limits>,
+    /// Set by `--log <filter>` and `--log-file <path>`.
+    pub trace: TraceOptions,
+    /// Set by `--year/-y`.
+    pub year: Option<i32>,
+}
+
+fn parse_input_source(args: &mut pico_args::Arguments) -> Result<InputSource, pico_args::Error> {
+    let example = args.contains("--example");
+    let path: Option<PathBuf> =
+        args.opt_value_from_os_str("--input", |s| Ok::<_, String>(PathBuf::from(s)))?;
+
+    match (example, path) {
+        (true, Some(_)) => Err(pico_args::Error::ArgumentParsingFailed {
+            cause: "--example and --input cannot be combined".to_string(),
+        }),
+        (true, None) => Ok(InputSource::Example),
+        (false, Some(path)) if path.as_os_str() == "-" => Ok(InputSource::Stdin),
+        (false, Some(path)) => Ok(InputSource::File(path)),
+        (false, None) => Ok(InputSource::Puzzle),
+    }
+}
+
+fn parse_bench_options(
+    args: &mut pico_args::Arguments,
+) -> Result<Option<BenchOptions>, pico_args::Error> {
+    let enabled = args.contains("--bench");
+    let warmup: Option<u32> = args.opt_value_from_str("--warmup")?;
+    let iterations: Option<u32> = args.opt_value_from_str("--iterations")?;
+    let time = args.opt_value_from_fn("--time", parse_seconds)?;
+
+    if !enabled {
+        if warmup.is_some() || iterations.is_some() || time.is_some() {
+            return Err(pico_args::Error::ArgumentParsingFailed {
+                cause: "--warmup, --iterations and --time require --bench".to_string(),
+            });
+        }
+        return Ok(None);
+    }
+
+    let defaults = BenchOptions::default();
+    let budget = match (iterations, time) {
+        (Some(_), Some(_)) => {
+            return Err(pico_args::Error::ArgumentParsingFailed {
+                cause: "--iterations and --time cannot be combined".to_string(),
+            })
+        }
+        (Some(0), None) => {
+            return Err(pico_args::Error::ArgumentParsingFailed {
+                cause: "--iterations needs to be at least 1".to_string(),
+            })
+        }
+        (Some(iterations), None) => Budget::Iterations(iterations),
+        (None, Some(time)) => Budget::Time(time),
+        (None, None) => defaults.budget,
+    };
+
+    Ok(Some(BenchOptions {
+        warmup: warmup.unwrap_or(defaults.warmup),
+        budget,
+    }))
+}
+
+fn parse_compare_options(
+    args: &mut pico_args::Arguments,
+) -> Result<Option<CompareOptions>, pico_args::Error> {
+    let enabled = args.contains("--compare");
+    let baseline: Option<String> = args.opt_value_from_str("--baseline")?;
+    let threshold: Option<f64> = args.opt_value_from_str("--threshold")?;
+
+    if !enabled {
+        if baseline.is_some() || threshold.is_some() {
+            return Err(pico_args::Error::ArgumentParsingFailed {
+                cause: "--baseline and --threshold require --compare".to_string(),
+            });
+        }
+        return Ok(None);
+    }
+
+    let defaults = CompareOptions::default();
+    let threshold = threshold.unwrap_or(defaults.threshold);
+    if !threshold.is_finite() || threshold < 0.0 {
+        return Err(pico_args::Error::ArgumentParsingFailed {
+            cause: format!("--threshold needs to be a non-negative percentage, got {threshold}"),
+        });
+    }
+
+    Ok(Some(CompareOptions {
+        baseline,
+        threshold,
+    }))
+}
+
+fn parse_trace_options(args: &mut pico_args::Arguments) -> Result<TraceOptions, pico_args::Error> {
+    Ok(TraceOptions {
+        filter: args.opt_value_from_fn("--log", str::parse::<Filter>)?,
+        file: args.opt_value_from_os_str("--log-file", |s| Ok::<_, String>(PathBuf::from(s)))?,
+    })
+}
+
+fn parse_limits(args: &mut pico_args::Arguments) -> Result<Option<Limits>, pico_args::Error> {
+    let part_timeout = args.opt_value_from_fn("--timeout", parse_seconds)?;
+    let day_timeout = args.opt_value_from_fn("--day-timeout", parse_seconds)?;
+    let memory: Option<usize> = args.opt_value_from_str("--memory-limit")?;
+
+    if part_timeout.is_none() && day_timeout.is_none() && memory.is_none() {
+        return Ok(None);
+    }
+    if memory == Some(0) {
+        return Err(pico_args::Error::ArgumentParsingFailed {
+            cause: "--memory-limit needs to be at least 1 MiB".to_string(),
+        });
+    }
+
+    let defaults = Limits::configured();
+    Ok(Some(Limits {
+        day_timeout: day_timeout.or(defaults.day_timeout),
+        part_timeout: part_timeout.or(defaults.part_timeout),
+        memory: memory
+            .map(|mebibytes| mebibytes.saturating_mul(1024 * 1024))
+            .or(defaults.memory),
+    }))
+}
+
+impl RunArgs {
+    pub fn from_env() -> Result<Self, pico_args::Error> {
+        Self::parse(pico_args::Arguments::from_env())
+    }
+
+    pub fn parse(mut args: pico_args::Arguments) -> Result<Self, pico_args::Error> {
+        let mut run_args = Self {
+            format: args
+                .opt_value_from_str("--format")?
+                .or(config::get().run.format)
+                .unwrap_or_default(),
+            bench: parse_bench_options(&mut args)?,
+            compare: parse_compare_options(&mut args)?,
+            accept: args.contains("--accept"),
+            input: parse_input_source(&mut args)?,
+            limits: parse_limits(&mut args)?,
+            trace: parse_trace_options(&mut args)?,
+            year: args.opt_value_from_str(["-y", "--year"])?,
+        };
+
+        if run_args.accept && run_args.bench.is_some() {
+            return Err(pico_args::Error::ArgumentParsingFailed {
+                cause: "--accept cannot be combined with --bench".to_string(),
+            });
+        }
+
+        if run_args.compare.is_some() && run_args.bench.is_none() {
+            return Err(pico_args::Error::ArgumentParsingFailed {
+                cause: "--compare requires --bench".to_string(),
+            });
+        }
+
+        let mut remaining = args.finish();
+        // A lone `-` reads the input from stdin, like `--input -`.
+        if let Some(index) = remaining
+            .iter()
+            .position(|arg| arg == "-")
+        {
+            if run_args.input != InputSource::Puzzle {
+                return Err(pico_args::Error::ArgumentParsingFailed {
+                    cause: "- cannot be combined with --example or --input".to_string(),
+                });
+            }
+            remaining.remove(index);
+            run_args.input = InputSource::Stdin;
+        }
+
+        if !run_args.input.is_puzzle() && (run_args.accept || run_args.compare.is_some()) {
+            return Err(pico_args::Error::ArgumentParsingFailed {
+                cause: "--accept and --compare only work with the puzzle input".to_string(),
+            });
+        }
+
+        if !remaining.is_empty() {
+            return Err(pico_args::Error::ArgumentParsingFailed {
+                cause: format!("unexpected arguments: {remaining:?}"),
+            });
+        }
+
+        Ok(run_args)
+    }
+}
+
+/// Arguments of the commands that talk to the Advent of Code website.
+#[derive(Debug, Default)]
+pub struct SiteArgs {
+    /// Set by `--year/-y`.
+    pub year: Option<i32>,
+    /// Set by `--base-url <url>`.
+    pub base_url: Option<String>,
+}
+
+impl SiteArgs {
+    pub fn parse(args: &mut pico_args::Arguments) -> Result<Self, pico_args::Error> {
+        Ok(Self {
+            year: args.opt_value_from_str(["-y", "--year"])?,
+            base_url: args.opt_value_from_str("--base-url")?,
+        })
+    }
+
+    /// The requested year, or the default year.
+    pub fn year(&self) -> i32 {
+        self.year
+            .unwrap_or_else(input::default_year)
+    }
+
+    /// A client for `--base-url`, falling back to the environment.
+    pub fn client(&self) -> Result<Client, ClientError> {
+        match &self.base_url {
+            Some(base_url) => client::find_session().map(|session| Client::new(base_url, &session)),
+            None => Client::from_env(),
+        }
+    }
+}
@@ -0,0 +1,325 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use crate::output::{csv_field, json_value, Format};
+use crate::{catch_panic, Solution, Status, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+use std::fmt::Display;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+/// How long to keep sampling a part for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Budget {
+    /// Take exactly this many samples.
+    Iterations(u32),
+    /// Take samples until this much time has been spent sampling.
+    Time(Duration),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BenchOptions {
+    /// Calls made before sampling starts, in addition to the initial call that computes the answer.
+    pub warmup: u32,
+    pub budget: Budget,
+}
+
+impl Default for BenchOptions {
+    fn default() -> Self {
+        Self {
+            warmup: 3,
+            budget: Budget::Time(Duration::from_secs(3)),
+        }
+    }
+}
+
+/// Summary statistics over the samples of one part.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Stats {
+    pub samples: usize,
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub std_dev: Duration,
+    /// Samples below `Q1 - 1.5 * IQR`.
+    pub low_outliers: usize,
+    /// Samples above `Q3 + 1.5 * IQR`.
+    pub high_outliers: usize,
+}
+
+/// Linearly interpolated percentile of already sorted `samples`.
+fn percentile(sorted: &[f64], p: f64) -> f64 {
+    let rank = p * (sorted.len() - 1) as f64;
+    let (lower, upper) = (rank.floor() as usize, rank.ceil() as usize);
+    sorted[lower] + (sorted[upper] - sorted[lower]) * (rank - lower as f64)
+}
+
+impl Stats {
+    pub fn from_samples(samples: &[Duration]) -> Option<Self> {
+        if samples.is_empty() {
+            return None;
+        }
+
+        let mut nanos: Vec<f64> = samples
+            .iter()
+            .map(|s| s.as_nanos() as f64)
+            .collect();
+        nanos.sort_by(|a, b| a.total_cmp(b));
+
+        let n = nanos.len() as f64;
+        let mean = nanos.iter().sum::<f64>() / n;
+        let variance = if nanos.len() > 1 {
+            nanos
+                .iter()
+                .map(|s| (s - mean).powi(2))
+                .sum::<f64>()
+                / (n - 1.0)
+        } else {
+            0.0
+        };
+
+        let (q1, q3) = (percentile(&nanos, 0.25), percentile(&nanos, 0.75));
+        let iqr = q3 - q1;
+        let (low_fence, high_fence) = (q1 - 1.5 * iqr, q3 + 1.5 * iqr);
+
+        Some(Self {
+            samples: nanos.len(),
+            min: Duration::from_nanos(nanos[0] as u64),
+            median: Duration::from_nanos(percentile(&nanos, 0.5) as u64),
+            mean: Duration::from_nanos(mean as u64),
+            std_dev: Duration::from_nanos(variance.sqrt() as u64),
+            low_outliers: nanos
+                .iter()
+                .filter(|s| **s < low_fence)
+                .count(),
+            high_outliers: nanos
+                .iter()
+                .filter(|s| **s > high_fence)
+                .count(),
+        })
+    }
+}
+
+/// The outcome of benchmarking one part. Only solved parts are sampled.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BenchResult {
+    pub part: u8,
+    pub status: Status,
+    pub answer: Option<String>,
+    pub stats: Option<Stats>,
+}
+
+fn bench_part<T: Display>(
+    part: u8,
+    options: &BenchOptions,
+    mut solver: impl FnMut() -> Option<T>,
+) -> BenchResult {
+    // The first call pays for cold caches, so it only provides the answer and is never sampled.
+    let answer = match catch_panic(&mut solver) {
+        Ok(Some(answer)) => answer.to_string(),
+        Ok(None) => {
+            return BenchResult {
+                part,
+                status: Status::Unsolved,
+                answer: None,
+                stats: None,
+            }
+        }
+        Err(message) => {
+            return BenchResult {
+                part,
+                status: Status::Panicked(message),
+                answer: None,
+                stats: None,
+            }
+        }
+    };
+
+    for _ in 0..options.warmup {
+        black_box(solver());
+    }
+
+    let mut samples = vec![];
+    let started = Instant::now();
+    loop {
+        let timer = Instant::now();
+        black_box(solver());
+        samples.push(timer.elapsed());
+
+        let done = match options.budget {
+            Budget::Iterations(iterations) => samples.len() >= iterations as usize,
+            Budget::Time(budget) => started.elapsed() >= budget,
+        };
+        if done {
+            break;
+        }
+    }
+
+    BenchResult {
+        part,
+        status: Status::Solved,
+        answer: Some(answer),
+        stats: Stats::from_samples(&samples),
+    }
+}
+
+/// Parses `input` once and benchmarks both parts of `S` against it.
+pub fn bench<S: Solution>(raw: &str, options: &BenchOptions) -> [BenchResult; 2] {
+    crate::trace::set_day(S::DAY);
+    let status = match catch_panic(|| S::parse(raw)) {
+        Ok(Ok(input)) => {
+            return [
+                bench_part(1, options, || S::part_one(&input)),
+                bench_part(2, options, || S::part_two(&input)),
+            ]
+        }
+        Ok(Err(error)) => Status::Invalid(error.locate(raw)),
+        Err(message) => Status::Panicked(message),
+    };
+    [1, 2].map(|part| BenchResult {
+        part,
+        status: status.clone(),
+        answer: None,
+        stats: None,
+    })
+}
+
+const CSV_HEADER: &str =
+    "day,part,answer,status,samples,min_ns,median_ns,mean_ns,std_dev_ns,low_outliers,high_outliers";
+
+fn print_text(result: &BenchResult) {
+    println!("🎄 {ANSI_BOLD}Part {}{ANSI_RESET} 🎄", result.part);
+    match (&result.status, &result.answer, &result.stats) {
+        (Status::Solved, Some(answer), Some(stats)) => {
+            println!("{answer}");
+            println!(
+                "{ANSI_ITALIC}min: {:.2?}, median: {:.2?}, mean: {:.2?} ± {:.2?} ({} samples, {} low / {} high outliers){ANSI_RESET}",
+                stats.min,
+                stats.median,
+                stats.mean,
+                stats.std_dev,
+                stats.samples,
+                stats.low_outliers,
+                stats.high_outliers,
+            );
+        }
+        (Status::Panicked(message), _, _) => println!("panicked: {message}"),
+        _ => println!("not solved."),
+    }
+}
+
+fn stat_columns(stats: Option<&Stats>) -> [String; 7] {
+    match stats {
+        Some(stats) => [
+            stats.samples.to_string(),
+            stats
+                .min
+                .as_nanos()
+                .to_string(),
+            stats
+                .median
+                .as_nanos()
+                .to_string(),
+            stats
+                .mean
+                .as_nanos()
+                .to_string(),
+            stats
+                .std_dev
+                .as_nanos()
+                .to_string(),
+            stats.low_outliers.to_string(),
+            stats
+                .high_outliers
+                .to_string(),
+        ],
+        None => Default::default(),
+    }
+}
+
+fn to_json(day: u8, result: &BenchResult) -> String {
+    let [samples, min, median, mean, std_dev, low, high] =
+        stat_columns(result.stats.as_ref()).map(|column| {
+            if column.is_empty() {
+                "null".to_string()
+            } else {
+                column
+            }
+        });
+    format!(
+        "{{\"day\":{day},\"part\":{},\"answer\":{},\"status\":\"{}\",\"samples\":{samples},\"min_ns\":{min},\"median_ns\":{median},\"mean_ns\":{mean},\"std_dev_ns\":{std_dev},\"low_outliers\":{low},\"high_outliers\":{high},\"message\":{}}}",
+        result.part,
+        json_value(result.answer.as_deref()),
+        result.status.name(),
+        json_value(result.status.message().as_deref()),
+    )
+}
+
+fn to_csv(day: u8, result: &BenchResult) -> String {
+    format!(
+        "{day},{},{},{},{}",
+        result.part,
+        csv_field(
+            result
+                .answer
+                .as_deref()
+                .unwrap_or_default()
+        ),
+        result.status.name(),
+        stat_columns(result.stats.as_ref()).join(","),
+    )
+}
+
+/// Prints benchmark results in the requested [`Format`].
+pub fn print_results(format: Format, day: u8, results: &[BenchResult]) {
+    match format {
+        Format::Text => match &results[0].status {
+            Status::Invalid(error) => println!("{error}"),
+            _ => results
+                .iter()
+                .for_each(print_text),
+        },
+        Format::Json => results
+            .iter()
+            .for_each(|result| println!("{}", to_json(day, result))),
+        Format::Csv => {
+            println!("{CSV_HEADER}");
+            results
+                .iter()
+                .for_each(|result| println!("{}", to_csv(day, result)));
+        }
+    }
+}
+
+/// Parses a duration given in seconds, e.g. `5` or `0.5`.
+pub fn parse_seconds(value: &str) -> Result<Duration, String> {
+    value
+        .parse::<f64>()
+        .ok()
+        .filter(|seconds| seconds.is_finite() && *seconds > 0.0)
+        .map(Duration::from_secs_f64)
+        .ok_or_else(|| format!("expected a positive number of seconds, got \"{value}\""))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stats() {
+        let samples: Vec<Duration> = [10, 12, 11, 13, 12, 11, 10, 12, 100]
+            .iter()
+            .map(|n| Duration::from_nanos(*n))
+            .collect();
+
+        let stats = Stats::from_samples(&samples).unwrap();
+        assert_eq!(stats.samples, 9);
+        assert_eq!(stats.min, Duration::from_nanos(10));
+        assert_eq!(stats.median, Duration::from_nanos(12));
+        assert_eq!(stats.mean, Duration::from_nanos(21));
+        assert_eq!(stats.std_dev, Duration::from_nanos(29));
+        assert_eq!((stats.low_outliers, stats.high_outliers), (0, 1));
+
+        assert_eq!(Stats::from_samples(&[]), None);
+    }
+}
@@ -1,84 +1,79 @@
+use advent_of_code::parse::parse_field;
+use advent_of_code::{ParseError, Solution};
 use std::cmp::Ordering;
-use std::str::FromStr;
 
-pub fn part_one(input: &str) -> Option<u32> {
-    let calories: Vec<Option<u32>> = input
-        .split('\n')
-        .map(|i| FromStr::from_str(i).ok())
-        .collect();
+pub struct Day01;
 
-    let mut current: u32 = 0;
-    let mut max: u32 = 0;
+impl Solution for Day01 {
+    const DAY: u8 = 1;
 
-    for calorie in calories {
-        let calorie = calorie.unwrap_or(0);
-        match calorie {
-            0 => match current.cmp(&max) {
-                Ordering::Greater => {
-                    max = current;
-                    current = 0
-                }
-                _ => current = 0,
-            },
-            _ => current += calorie,
-        }
+    type Input = Vec<Option<u32>>;
+    type PartOne = u32;
+    type PartTwo = u32;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        // Empty lines separate the elves and are kept as `None`.
+        input
+            .split('\n')
+            .map(|i| match i {
+                "" => Ok(None),
+                _ => parse_field(i).map(Some),
+            })
+            .collect()
     }
-    Some(max)
-}
 
-pub fn part_two(input: &str) -> Option<u32> {
-    let calories: Vec<Option<u32>> = input
-        .split('\n')
-        .map(|i| FromStr::from_str(i).ok())
-        .collect();
+    fn part_one(calories: &Self::Input) -> Option<u32> {
+        let mut current: u32 = 0;
+        let mut max: u32 = 0;
 
-    let mut current: u32 = 0;
-    let mut first: u32 = 0;
-    let mut second: u32 = 0;
-    let mut third: u32 = 0;
+        for calorie in calories {
+            let calorie = calorie.unwrap_or(0);
+            match calorie {
+                0 => match current.cmp(&max) {
+                    Ordering::Greater => {
+                        max = current;
+                        current = 0
+                    }
+                    _ => current = 0,
+                },
+                _ => current += calorie,
+            }
+        }
+        Some(max)
+    }
 
-    // Pretty naive solution. Might come back to this.
-    for calorie in calories {
-        let calorie = calorie.unwrap_or(0);
-        match calorie {
-            0 => {
-                if current > first {
-                    third = second;
-                    second = first;
-                    first = current
-                } else if current > second {
-                    third = second;
-                    second = current
-                } else if current > third {
-                    third = current
+    fn part_two(calories: &Self::Input) -> Option<u32> {
+        let mut current: u32 = 0;
+        let mut first: u32 = 0;
+        let mut second: u32 = 0;
+        let mut third: u32 = 0;
+
+        // Pretty naive solution. Might come back to this.
+        for calorie in calories {
+            let calorie = calorie.unwrap_or(0);
+            match calorie {
+                0 => {
+                    if current > first {
+                        third = second;
+                        second = first;
+                        first = current
+                    } else if current > second {
+                        third = second;
+                        second = current
+                    } else if current > third {
+                        third = current
+                    }
+                    current = 0
                 }
-                current = 0
+                _ => current += calorie,
             }
-            _ => current += calorie,
         }
+        Some(first + second + third)
     }
-    Some(first + second + third)
 }
 
 fn main() {
-    let input = &advent_of_code::read_file("inputs", 1);
-    advent_of_code::solve!(1, part_one, input);
-    advent_of_code::solve!(2, part_two, input);
+    advent_of_code::solve!(Day01);
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let input = advent_of_code::read_file("examples", 1);
-        assert_eq!(part_one(&input), Some(24000));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let input = advent_of_code::read_file("examples", 1);
-        assert_eq!(part_two(&input), Some(45000));
-    }
-}
+advent_of_code::example_tests!(Day01);
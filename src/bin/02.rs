@@ -1,6 +1,8 @@
+use advent_of_code::parse::parse_field;
+use advent_of_code::{ParseError, Solution};
 use strum::*;
 
-#[derive(EnumString)]
+#[derive(EnumString, Clone, Copy)]
 pub enum OpponentChoice {
     A,
     B,
@@ -19,7 +21,7 @@ impl OpponentChoice {
     }
 }
 
-#[derive(EnumString)]
+#[derive(EnumString, Clone, Copy)]
 pub enum MyChoice {
     X,
     Y,
@@ -38,7 +40,7 @@ impl MyChoice {
     }
 }
 
-#[derive(EnumString)]
+#[derive(EnumString, Clone, Copy)]
 pub enum DesiredOutcome {
     X,
     Y,
@@ -58,6 +60,7 @@ impl DesiredOutcome {
     }
 }
 
+#[derive(Clone, Copy)]
 pub enum Choice {
     Rock,
     Paper,
@@ -184,96 +187,68 @@ impl Outcome {
     }
 }
 
-fn strip_trailing_newline(input: &str) -> &str {
-    input
-        .strip_suffix("\r\n")
-        .or_else(|| input.strip_suffix('\n'))
-        .unwrap_or(input)
-}
+pub struct Day02;
 
-pub fn part_one(input: &str) -> Option<u32> {
-    // Strip trailing newline to avoid panicking at
-    // 'called `Result::unwrap()` on an `Err` value: VariantNotFound'.
-    // Would be better to handle the error rather than eliminate a single possible one.
-    let input = strip_trailing_newline(input);
+impl Solution for Day02 {
+    const DAY: u8 = 2;
 
-    let choices: Vec<Vec<&str>> = input
-        .split('\n')
-        .map(|i| i.split(' ').collect())
-        .collect();
+    // The second column is interpreted as a choice in part one and as an outcome in part two.
+    type Input = Vec<(OpponentChoice, MyChoice, DesiredOutcome)>;
+    type PartOne = u32;
+    type PartTwo = u32;
 
-    let mut score = 0;
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        input
+            .lines()
+            .map(|line| {
+                let (opponent, me) = line
+                    .split_once(' ')
+                    .ok_or_else(|| {
+                        ParseError::new(line, "expected two columns separated by a space")
+                    })?;
+                Ok((
+                    parse_field::<OpponentChoice>(opponent)?,
+                    parse_field::<MyChoice>(me)?,
+                    parse_field::<DesiredOutcome>(me)?,
+                ))
+            })
+            .collect()
+    }
 
-    for choice in choices {
-        let opponent_choice: Choice = choice[0]
-            .parse::<OpponentChoice>()
-            .unwrap()
-            .to_choice();
-        let my_choice: Choice = choice[1]
-            .parse::<MyChoice>()
-            .unwrap()
-            .to_choice();
+    fn part_one(choices: &Self::Input) -> Option<u32> {
+        let mut score = 0;
 
-        let outcome: Outcome = Outcome::new(&my_choice, &opponent_choice);
+        for (opponent_choice, my_choice, _) in choices {
+            let opponent_choice: Choice = opponent_choice.to_choice();
+            let my_choice: Choice = my_choice.to_choice();
 
-        score += outcome.value() + my_choice.value()
-    }
+            let outcome: Outcome = Outcome::new(&my_choice, &opponent_choice);
 
-    Some(score)
-}
+            score += outcome.value() + my_choice.value()
+        }
 
-pub fn part_two(input: &str) -> Option<u32> {
-    // Strip trailing newline to avoid panicking at
-    // 'called `Result::unwrap()` on an `Err` value: VariantNotFound'.
-    // Would be better to handle the error rather than eliminate a single possible one.
-    let input = strip_trailing_newline(input);
-
-    let strategies: Vec<Vec<&str>> = input
-        .split('\n')
-        .map(|i| i.split(' ').collect())
-        .collect();
-
-    let mut score = 0;
-
-    for strategy in strategies {
-        let opponent_choice: Choice = strategy[0]
-            .parse::<OpponentChoice>()
-            .unwrap()
-            .to_choice();
-        let desired_outcome: Outcome = strategy[1]
-            .parse::<DesiredOutcome>()
-            .unwrap()
-            .to_outcome();
-
-        let my_choice: Choice = Choice::from_outcome(&desired_outcome, &opponent_choice);
-        let outcome: Outcome = Outcome::new(&my_choice, &opponent_choice);
-
-        score += outcome.value() + my_choice.value()
+        Some(score)
     }
 
-    Some(score)
-}
+    fn part_two(strategies: &Self::Input) -> Option<u32> {
+        let mut score = 0;
 
-fn main() {
-    let input = &advent_of_code::read_file("inputs", 2);
+        for (opponent_choice, _, desired_outcome) in strategies {
+            let opponent_choice: Choice = opponent_choice.to_choice();
+            let desired_outcome: Outcome = desired_outcome.to_outcome();
 
-    advent_of_code::solve!(1, part_one, input);
-    advent_of_code::solve!(2, part_two, input);
-}
+            let my_choice: Choice = Choice::from_outcome(&desired_outcome, &opponent_choice);
+            let outcome: Outcome = Outcome::new(&my_choice, &opponent_choice);
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+            score += outcome.value() + my_choice.value()
+        }
 
-    #[test]
-    fn test_part_one() {
-        let input = advent_of_code::read_file("examples", 2);
-        assert_eq!(part_one(&input), Some(15));
+        Some(score)
     }
+}
 
-    #[test]
-    fn test_part_two() {
-        let input = advent_of_code::read_file("examples", 2);
-        assert_eq!(part_two(&input), Some(12));
-    }
+fn main() {
+    advent_of_code::solve!(Day02);
 }
+
+advent_of_code::example_tests!(Day02);
@@ -1,120 +1,81 @@
-use itertools::Itertools;
+use advent_of_code::parse::parse_field;
+use advent_of_code::{ParseError, Solution};
 
-pub fn part_one(input: &str) -> Option<u32> {
-    let assignments: Vec<_> = input
-        // Split on newlines to get all pairs.
-        .lines()
-        .map(|pair| {
-            // For every pair, split on commas to get the sections.
-            pair.split(',')
-                .map(|sections| {
-                    // For every section range, split on hyphens to get the lower and upper bounds
-                    // of the section range.
-                    sections
-                        .split('-')
-                        .map(|section| {
-                            // For every section bound, parse to `u32`.
-                            section
-                                .parse::<u32>()
-                                .unwrap()
-                        })
-                        // Collect the section bounds to a tuple. This simplifies the `filter`
-                        // expression for constructing `overlaps` below.
-                        // Very useful!
-                        .collect_tuple::<(u32, u32)>()
-                        .unwrap()
-                })
-                // Collect the section ranges to a tuple. This simplifies the `filter`
-                // expression for constructing `overlaps` below.
-                .collect_tuple()
-                .unwrap()
-        })
-        // Collect to the `assignments` vector.
-        .collect();
-
-    #[rustfmt::skip]
-    let overlaps: u32 = assignments
-        .iter()
-        // Filter for pairs where one the section ranges fully contains the other.
-        .filter(|(a, b)| {
-            ((a.0 <= b.0) && (a.1 >= b.1))
-            || ((b.0 <= a.0) && (b.1 >= a.1))
-        })
-        // Count the number of instances and try into `u32`.
-        .count()
-        .try_into()
-        .unwrap();
+pub type SectionRange = (u32, u32);
 
-    Some(overlaps)
+pub fn parse_section_range(sections: &str) -> Result<SectionRange, ParseError> {
+    // Split on the hyphen to get the lower and upper bounds of the section range.
+    let (lower, upper) = sections
+        .split_once('-')
+        .ok_or_else(|| ParseError::new(sections, "expected a section range like `2-4`"))?;
+    Ok((parse_field(lower)?, parse_field(upper)?))
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
-    let assignments: Vec<_> = input
-        // Split on newlines to get all pairs.
-        .lines()
-        .map(|pair| {
-            // For every pair, split on commas to get the sections.
-            pair.split(',')
-                .map(|sections| {
-                    // For every section range, split on hyphens to get the lower and upper bounds
-                    // of the section range.
-                    sections
-                        .split('-')
-                        .map(|section| {
-                            // For every section bound, parse to `u32`.
-                            section
-                                .parse::<u32>()
-                                .unwrap()
-                        })
-                        // Collect the section bounds to a tuple. This simplifies the `filter`
-                        // expression for constructing `overlaps` below.
-                        // Very useful!
-                        .collect_tuple::<(u32, u32)>()
-                        .unwrap()
-                })
-                // Collect the section ranges to a tuple. This simplifies the `filter`
-                // expression for constructing `overlaps` below.
-                .collect_tuple()
-                .unwrap()
-        })
-        // Collect to the `assignments` vector.
-        .collect();
+pub struct Day04;
 
-    #[rustfmt::skip]
-    let overlaps: u32 = assignments
-        .iter()
-        // Filter for pairs where one the section ranges partially contains the other.
-        .filter(|(a, b)| {
-            ((a.0 <= b.0) && (a.1 >= b.0))
-            || ((b.0 <= a.0) && (b.1 >= a.0))
-        })
-        // Count the number of instances and try into `u32`.
-        .count()
-        .try_into()
-        .unwrap();
+impl Solution for Day04 {
+    const DAY: u8 = 4;
 
-    Some(overlaps)
-}
+    type Input = Vec<(SectionRange, SectionRange)>;
+    type PartOne = u32;
+    type PartTwo = u32;
 
-fn main() {
-    let input = &advent_of_code::read_file("inputs", 4);
-    advent_of_code::solve!(1, part_one, input);
-    advent_of_code::solve!(2, part_two, input);
-}
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        input
+            // Split on newlines to get all pairs.
+            .lines()
+            .map(|pair| {
+                // For every pair, split on the comma to get the sections.
+                let (first, second) = pair
+                    .split_once(',')
+                    .ok_or_else(|| {
+                        ParseError::new(pair, "expected two section ranges separated by a comma")
+                    })?;
+                // Collect the section ranges to a tuple. This simplifies the `filter`
+                // expressions for constructing `overlaps` in both parts.
+                Ok((parse_section_range(first)?, parse_section_range(second)?))
+            })
+            // Collect to the `assignments` vector, stopping at the first invalid pair.
+            .collect()
+    }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    fn part_one(assignments: &Self::Input) -> Option<u32> {
+        #[rustfmt::skip]
+        let overlaps: u32 = assignments
+            .iter()
+            // Filter for pairs where one the section ranges fully contains the other.
+            .filter(|(a, b)| {
+                ((a.0 <= b.0) && (a.1 >= b.1))
+                || ((b.0 <= a.0) && (b.1 >= a.1))
+            })
+            // Count the number of instances and try into `u32`.
+            .count()
+            .try_into()
+            .unwrap();
 
-    #[test]
-    fn test_part_one() {
-        let input = advent_of_code::read_file("examples", 4);
-        assert_eq!(part_one(&input), Some(2));
+        Some(overlaps)
     }
 
-    #[test]
-    fn test_part_two() {
-        let input = advent_of_code::read_file("examples", 4);
-        assert_eq!(part_two(&input), Some(4));
+    fn part_two(assignments: &Self::Input) -> Option<u32> {
+        #[rustfmt::skip]
+        let overlaps: u32 = assignments
+            .iter()
+            // Filter for pairs where one the section ranges partially contains the other.
+            .filter(|(a, b)| {
+                ((a.0 <= b.0) && (a.1 >= b.0))
+                || ((b.0 <= a.0) && (b.1 >= a.0))
+            })
+            // Count the number of instances and try into `u32`.
+            .count()
+            .try_into()
+            .unwrap();
+
+        Some(overlaps)
     }
 }
+
+fn main() {
+    advent_of_code::solve!(Day04);
+}
+
+advent_of_code::example_tests!(Day04);
@@ -1,88 +1,117 @@
-use itertools::Itertools;
-
-// https://users.rust-lang.org/t/solved-how-to-split-string-into-multiple-sub-strings-with-given-length/10542/9
-// Thank you!
-pub fn sub_strings(string: String, sub_len: usize) -> Vec<String> {
-    let mut subs = Vec::with_capacity(string.len() / sub_len);
-    let mut iter = string.chars();
-    let mut pos = 0;
-
-    while pos < string.len() {
-        let mut len = 0;
-        for ch in iter.by_ref().take(sub_len) {
-            len += ch.len_utf8();
-        }
-        subs.push(string[pos..pos + len - 1].to_string());
-        pos += len;
-    }
-    subs
-}
+use advent_of_code::parse::parse_field;
+use advent_of_code::{ParseError, Solution};
 
-pub fn split_input(input: &str) -> (&str, &str) {
+/// A `(count, from, to)` move of crates between stacks.
+pub type Procedure = (usize, usize, usize);
+
+pub fn split_input(input: &str) -> Result<(&str, &str), ParseError> {
     input
         .split_once("\n\n")
-        .unwrap()
+        .ok_or_else(|| {
+            ParseError::new(
+                input,
+                "expected stacks and procedures separated by an empty line",
+            )
+        })
 }
 
-pub fn parse_stacks(stack_rows: &str) -> Vec<Vec<char>> {
-    // Parse stack configuration into rows of items
-    let mut stack_rows: Vec<Vec<String>> = stack_rows
-        .lines()
-        .map(|l| l.to_string())
-        .map(|mut l| {
-            l.push(' ');
-            l
-        })
-        .map(|l| sub_strings(l, 4))
-        .collect();
-
-    let stack_width = stack_rows[stack_rows.len() - 1].len();
-    // Remove stack numbers
-    stack_rows.pop();
-    stack_rows.reverse();
-    // Hack to hopefully not index out of bounds
-    let stack_height = stack_rows.len() * 2;
-
-    // Invert 2D vector so that outer dimension consists in stacks rather than rows.
-    let mut stacks: Vec<Vec<char>> = vec![vec![' '; stack_width]; stack_height];
-    for (row_index, row) in stack_rows
-        .into_iter()
-        .enumerate()
-    {
-        for (item_index, item) in row.into_iter().enumerate() {
-            let item = item.chars().nth(1).unwrap();
-            if item == ' ' {
-                continue;
+pub fn parse_stacks(stack_rows: &str) -> Result<Vec<Vec<char>>, ParseError> {
+    // The last row numbers the stacks, the rows above it hold the crates.
+    let mut rows: Vec<&str> = stack_rows.lines().collect();
+    let numbers = rows
+        .pop()
+        .unwrap_or(stack_rows);
+    let mut stack_count = 0;
+    for number in numbers.split_whitespace() {
+        stack_count += 1;
+        if parse_field::<usize>(number)? != stack_count {
+            return Err(ParseError::new(
+                number,
+                format!("expected stack number {stack_count}"),
+            ));
+        }
+    }
+    if stack_count == 0 {
+        return Err(ParseError::new(
+            numbers,
+            "expected a row of stack numbers below the stacks",
+        ));
+    }
+
+    // Build the stacks bottom up, so that the top item of each stack comes last.
+    let mut stacks: Vec<Vec<char>> = vec![vec![]; stack_count];
+    for row in rows.into_iter().rev() {
+        if let Some(extra) = row
+            .get(stack_count * 4 - 1..)
+            .filter(|extra| !extra.trim().is_empty())
+        {
+            return Err(ParseError::new(
+                extra,
+                format!("expected at most {stack_count} stacks"),
+            ));
+        }
+        for (index, stack) in stacks.iter_mut().enumerate() {
+            if let Some(item) = parse_crate(row, index)? {
+                stack.push(item);
             }
-            stacks[item_index][row_index] = item;
         }
     }
+    Ok(stacks)
+}
 
-    // Filter out `' '` from stacks.
-    stacks
-        .into_iter()
-        .map(|v| {
-            v.into_iter()
-                .filter(|c| c != &' ')
-                .collect()
-        })
-        .collect()
+/// The crate of stack `index` in a row of crates, or `None` if the stack does not reach it.
+fn parse_crate(row: &str, index: usize) -> Result<Option<char>, ParseError> {
+    let start = (index * 4).min(row.len());
+    let end = (start + 3).min(row.len());
+    let Some(cell) = row.get(start..end) else {
+        return Err(ParseError::new(row, "expected crates like `[A]`"));
+    };
+    match cell.as_bytes() {
+        _ if cell.trim().is_empty() => Ok(None),
+        [b'[', item, b']'] if item.is_ascii_alphabetic() => Ok(Some(char::from(*item))),
+        _ => Err(ParseError::new(cell, "expected a crate like `[A]`")),
+    }
 }
 
-pub fn parse_procedures(procedures: &str) -> Vec<(usize, usize, usize)> {
+pub fn parse_procedures(
+    procedures: &str,
+    stack_count: usize,
+) -> Result<Vec<Procedure>, ParseError> {
     // Build procedures
     procedures
         .lines()
         .map(|l| {
-            l.to_string()
-                .split_whitespace()
-                .filter_map(|s| s.parse::<usize>().ok())
-                .collect_tuple::<(usize, usize, usize)>()
-                .unwrap()
+            let words: Vec<&str> = l.split_whitespace().collect();
+            match words[..] {
+                ["move", count, "from", from, "to", to] => Ok((
+                    parse_stack_count(count)?,
+                    parse_stack_number(from, stack_count)?,
+                    parse_stack_number(to, stack_count)?,
+                )),
+                _ => Err(ParseError::new(
+                    l,
+                    "expected `move <count> from <stack> to <stack>`",
+                )),
+            }
         })
         .collect()
 }
 
+fn parse_stack_count(count: &str) -> Result<usize, ParseError> {
+    parse_field(count)
+}
+
+fn parse_stack_number(stack: &str, stack_count: usize) -> Result<usize, ParseError> {
+    match parse_field(stack)? {
+        0 => Err(ParseError::new(stack, "stacks are numbered starting at 1")),
+        number if number > stack_count => Err(ParseError::new(
+            stack,
+            format!("there are only {stack_count} stacks"),
+        )),
+        number => Ok(number),
+    }
+}
+
 pub fn read_top_items(stacks: Vec<Vec<char>>) -> String {
     // Extract the top item from each stack into a `String`
     stacks
@@ -91,71 +120,108 @@ pub fn read_top_items(stacks: Vec<Vec<char>>) -> String {
             Some(c) => c.to_string(),
             None => "".to_owned(),
         })
-        .into_iter()
         .collect()
 }
 
-pub fn part_one(input: &str) -> Option<String> {
-    // Could definitely have done this one cleaner. But I'm slow enough already!
-    let (stack_rows, procedures) = split_input(input);
+pub struct Day05;
 
-    let mut stacks = parse_stacks(stack_rows);
+impl Solution for Day05 {
+    const DAY: u8 = 5;
 
-    let procedures = parse_procedures(procedures);
+    type Input = (Vec<Vec<char>>, Vec<Procedure>);
+    type PartOne = String;
+    type PartTwo = String;
 
-    // Apply procedures
-    for p in procedures {
-        for _ in 0..p.0 {
-            let item = stacks[p.1 - 1].pop().unwrap();
-            stacks[p.2 - 1].push(item);
-        }
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        // Could definitely have done this one cleaner. But I'm slow enough already!
+        let (stack_rows, procedures) = split_input(input)?;
+
+        let stacks = parse_stacks(stack_rows)?;
+        let procedures = parse_procedures(procedures, stacks.len())?;
+        Ok((stacks, procedures))
     }
 
-    let top_items = read_top_items(stacks);
-    Some(top_items)
-}
+    fn part_one((stacks, procedures): &Self::Input) -> Option<String> {
+        let mut stacks = stacks.clone();
 
-pub fn part_two(input: &str) -> Option<String> {
-    let (stack_rows, procedures) = split_input(input);
+        // Apply procedures
+        for p in procedures {
+            // Like in part two, moving more crates than a stack holds moves all of them.
+            for _ in 0..p.0 {
+                if let Some(item) = stacks[p.1 - 1].pop() {
+                    stacks[p.2 - 1].push(item);
+                }
+            }
+        }
 
-    let mut stacks = parse_stacks(stack_rows);
+        let top_items = read_top_items(stacks);
+        Some(top_items)
+    }
 
-    let procedures = parse_procedures(procedures);
+    fn part_two((stacks, procedures): &Self::Input) -> Option<String> {
+        let mut stacks = stacks.clone();
+
+        // Apply procedures
+        for p in procedures {
+            let source_stack = &mut stacks[p.1 - 1];
+            let mut items = source_stack.split_off(
+                source_stack
+                    .len()
+                    .saturating_sub(p.0),
+            );
+            stacks[p.2 - 1].append(&mut items);
+        }
 
-    // Apply procedures
-    for p in procedures {
-        let source_stack = &mut stacks[p.1 - 1];
-        let mut items = source_stack.split_off(
-            source_stack
-                .len()
-                .saturating_sub(p.0),
-        );
-        stacks[p.2 - 1].append(&mut items);
+        let top_items = read_top_items(stacks);
+        Some(top_items)
     }
-
-    let top_items = read_top_items(stacks);
-    Some(top_items)
 }
 
 fn main() {
-    let input = &advent_of_code::read_file("inputs", 5);
-    advent_of_code::solve!(1, part_one, input);
-    advent_of_code::solve!(2, part_two, input);
+    advent_of_code::solve!(Day05);
 }
 
+advent_of_code::example_tests!(Day05);
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    #[test]
-    fn test_part_one() {
-        let input = advent_of_code::read_file("examples", 5);
-        assert_eq!(part_one(&input), Some("CMZ".to_string()));
+    fn parse_error(input: &str) -> String {
+        Day05::parse(input)
+            .map(|_| ())
+            .unwrap_err()
+            .locate(input)
+            .summary()
     }
 
     #[test]
-    fn test_part_two() {
-        let input = advent_of_code::read_file("examples", 5);
-        assert_eq!(part_two(&input), Some("MCD".to_string()));
+    fn test_parse_rejects_malformed_input() {
+        let input = "    [D]\n[N] [C]\n 1   2\n\nmove 1 from 2 to 1\n";
+        assert_eq!(
+            Day05::parse(input).unwrap().0,
+            vec![vec!['N'], vec!['C', 'D']]
+        );
+
+        assert_eq!(
+            parse_error("\n\nmove 1 from 2 to 1"),
+            "line 1, column 1: expected a row of stack numbers below the stacks"
+        );
+        assert_eq!(
+            parse_error("[N] [C\n 1   2\n\n"),
+            "line 1, column 5: expected a crate like `[A]`"
+        );
+        assert_eq!(
+            parse_error("[N] [C] [D]\n 1   2\n\n"),
+            "line 1, column 8: expected at most 2 stacks"
+        );
+        assert_eq!(
+            parse_error("[N] [C]\n 1   2\n\nmove 1 from 3 to 1"),
+            "line 4, column 13: there are only 2 stacks"
+        );
+        assert_eq!(
+            parse_error("[N] [C]\n 1   2\n\nmove 1 from 0 to 1"),
+            "line 4, column 13: stacks are numbered starting at 1"
+        );
     }
 }
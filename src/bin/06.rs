@@ -1,3 +1,4 @@
+use advent_of_code::{ParseError, Solution};
 use itertools::Itertools;
 
 pub fn find_marker_idx(packet: &str, unique_characters: usize) -> Option<usize> {
@@ -22,33 +23,30 @@ pub fn find_marker_idx(packet: &str, unique_characters: usize) -> Option<usize>
     marker_idx
 }
 
-pub fn part_one(input: &str) -> Option<usize> {
-    find_marker_idx(input, 4)
-}
+pub struct Day06;
 
-pub fn part_two(input: &str) -> Option<usize> {
-    find_marker_idx(input, 14)
-}
+impl Solution for Day06 {
+    const DAY: u8 = 6;
 
-fn main() {
-    let input = &advent_of_code::read_file("inputs", 6);
-    advent_of_code::solve!(1, part_one, input);
-    advent_of_code::solve!(2, part_two, input);
-}
+    type Input = String;
+    type PartOne = usize;
+    type PartTwo = usize;
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        Ok(input.to_string())
+    }
 
-    #[test]
-    fn test_part_one() {
-        let input = advent_of_code::read_file("examples", 6);
-        assert_eq!(part_one(&input), Some(7));
+    fn part_one(packet: &Self::Input) -> Option<usize> {
+        find_marker_idx(packet, 4)
     }
 
-    #[test]
-    fn test_part_two() {
-        let input = advent_of_code::read_file("examples", 6);
-        assert_eq!(part_two(&input), Some(19));
+    fn part_two(packet: &Self::Input) -> Option<usize> {
+        find_marker_idx(packet, 14)
     }
 }
+
+fn main() {
+    advent_of_code::solve!(Day06);
+}
+
+advent_of_code::example_tests!(Day06);
@@ -1,7 +1,9 @@
+use advent_of_code::parse::parse_field;
+use advent_of_code::{ParseError, Solution};
 use itertools::Itertools;
 use std::collections::HashMap;
 
-pub fn build_filesystem(input: &str) -> HashMap<String, u64> {
+pub fn build_filesystem(input: &str) -> Result<HashMap<String, u64>, ParseError> {
     let mut fs: HashMap<String, u64> = HashMap::new();
     let root_dir = String::from("/");
     let mut current_dir: String = root_dir.clone();
@@ -32,12 +34,18 @@ pub fn build_filesystem(input: &str) -> HashMap<String, u64> {
                 _ => continue,
             },
             Some("dir") => {
-                let mut dir = String::from(elements.next().unwrap());
+                let mut dir = String::from(
+                    elements
+                        .next()
+                        .ok_or_else(|| {
+                            ParseError::new(line, "expected a directory name after `dir`")
+                        })?,
+                );
                 dir = current_dir.clone() + &dir + "/";
                 fs.insert(dir, 0);
             }
             Some(size) => {
-                let size: u64 = size.parse().unwrap();
+                let size: u64 = parse_field(size)?;
                 fs.entry(current_dir.clone())
                     .and_modify(|s| *s += size)
                     .or_insert(0);
@@ -60,51 +68,46 @@ pub fn build_filesystem(input: &str) -> HashMap<String, u64> {
             _ => continue,
         }
     }
-    fs
+    Ok(fs)
 }
 
-pub fn part_one(input: &str) -> Option<u64> {
-    let mut fs = build_filesystem(input);
+pub struct Day07;
 
-    fs.retain(|_, v| v <= &mut 100000);
+impl Solution for Day07 {
+    const DAY: u8 = 7;
 
-    let total: u64 = fs.values().sum();
-    Some(total)
-}
-
-pub fn part_two(input: &str) -> Option<u64> {
-    let mut fs = build_filesystem(input);
-    let total_size = fs.get("/").unwrap();
-    let mut amount_to_delete: u64 = 30_000_000 - (70_000_000 - total_size);
+    type Input = HashMap<String, u64>;
+    type PartOne = u64;
+    type PartTwo = u64;
 
-    fs.retain(|_, v| v >= &mut amount_to_delete);
-    let amount_deleted: u64 = *fs
-        .values()
-        .sorted()
-        .next()
-        .unwrap();
-    Some(amount_deleted)
-}
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        build_filesystem(input)
+    }
 
-fn main() {
-    let input = &advent_of_code::read_file("inputs", 7);
-    advent_of_code::solve!(1, part_one, input);
-    advent_of_code::solve!(2, part_two, input);
-}
+    fn part_one(fs: &Self::Input) -> Option<u64> {
+        let total: u64 = fs
+            .values()
+            .filter(|v| **v <= 100000)
+            .sum();
+        Some(total)
+    }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    fn part_two(fs: &Self::Input) -> Option<u64> {
+        let total_size = fs.get("/").unwrap();
+        let amount_to_delete: u64 = 30_000_000 - (70_000_000 - total_size);
 
-    #[test]
-    fn test_part_one() {
-        let input = advent_of_code::read_file("examples", 7);
-        assert_eq!(part_one(&input), Some(95_437));
+        let amount_deleted: u64 = *fs
+            .values()
+            .filter(|v| **v >= amount_to_delete)
+            .sorted()
+            .next()
+            .unwrap();
+        Some(amount_deleted)
     }
+}
 
-    #[test]
-    fn test_part_two() {
-        let input = advent_of_code::read_file("examples", 7);
-        assert_eq!(part_two(&input), Some(24_933_642));
-    }
+fn main() {
+    advent_of_code::solve!(Day07);
 }
+
+advent_of_code::example_tests!(Day07);
@@ -1,3 +1,5 @@
+use advent_of_code::{ParseError, Solution};
+
 pub enum RangeType {
     Horizontal,
     Vertical,
@@ -70,73 +72,6 @@ pub fn check_visibility<T, U>(
     }
 }
 
-pub fn part_one(input: &str) -> Option<usize> {
-    let trees = input
-        .lines()
-        .map(|l| {
-            l.chars()
-                .map(|c| c.to_digit(10).unwrap())
-                .collect::<Vec<u32>>()
-        })
-        .collect::<Vec<_>>();
-
-    let mut visibilities = trees
-        .iter()
-        .map(|l| {
-            l.iter()
-                .map(|_| false)
-                .collect::<Vec<bool>>()
-        })
-        .collect::<Vec<_>>();
-
-    let height = trees.len();
-    let width = trees.first()?.len();
-
-    // Check from left to right, going down the rows of trees
-    check_visibility(
-        0..height,
-        0..width,
-        &trees,
-        &mut visibilities,
-        RangeType::Vertical,
-    );
-    // Check from right to left, going down the rows of trees
-    check_visibility(
-        0..height,
-        (0..width).rev(),
-        &trees,
-        &mut visibilities,
-        RangeType::Vertical,
-    );
-    // Check from top to bottom, going across the columns of trees
-    check_visibility(
-        0..width,
-        0..height,
-        &trees,
-        &mut visibilities,
-        RangeType::Horizontal,
-    );
-    // Check from bottom to top, going across the columns of trees
-    check_visibility(
-        0..width,
-        (0..height).rev(),
-        &trees,
-        &mut visibilities,
-        RangeType::Horizontal,
-    );
-
-    let number_of_trees: usize = visibilities
-        .iter()
-        .map(|l| {
-            l.iter()
-                .filter(|c| **c)
-                .count()
-        })
-        .sum();
-
-    Some(number_of_trees)
-}
-
 pub fn check_vertical_score<T>(range: T, x: usize, trees: &[Vec<u32>]) -> usize
 where
     T: std::clone::Clone + std::iter::Iterator<Item = usize>,
@@ -183,56 +118,116 @@ where
     range.count() - 1
 }
 
-pub fn part_two(input: &str) -> Option<usize> {
-    let trees = input
-        .lines()
-        .map(|l| {
-            l.chars()
-                .map(|c| c.to_digit(10).unwrap())
-                .collect::<Vec<u32>>()
-        })
-        .collect::<Vec<_>>();
-
-    let height = trees.len() - 1;
-    let width = trees.first()?.len() - 1;
-    let mut highest_score: usize = 0;
-
-    for (y, tree_row) in trees.iter().enumerate() {
-        for (x, _tree) in tree_row.iter().enumerate() {
-            let top = check_vertical_score((0..=y).rev(), x, &trees);
-            let bottom = check_vertical_score(y..=height, x, &trees);
-            let left = check_horizontal_score((0..=x).rev(), y, &trees);
-            let right = check_horizontal_score(x..=width, y, &trees);
-
-            let score = top * bottom * left * right;
-            if score > highest_score {
-                highest_score = score;
+pub struct Day08;
+
+impl Solution for Day08 {
+    const DAY: u8 = 8;
+
+    type Input = Vec<Vec<u32>>;
+    type PartOne = usize;
+    type PartTwo = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        input
+            .lines()
+            .map(|l| {
+                l.char_indices()
+                    .map(|(i, c)| {
+                        c.to_digit(10).ok_or_else(|| {
+                            ParseError::new(
+                                &l[i..i + c.len_utf8()],
+                                "expected a tree height from 0 to 9",
+                            )
+                        })
+                    })
+                    .collect::<Result<Vec<u32>, ParseError>>()
+            })
+            .collect()
+    }
+
+    fn part_one(trees: &Self::Input) -> Option<usize> {
+        let mut visibilities = trees
+            .iter()
+            .map(|l| {
+                l.iter()
+                    .map(|_| false)
+                    .collect::<Vec<bool>>()
+            })
+            .collect::<Vec<_>>();
+
+        let height = trees.len();
+        let width = trees.first()?.len();
+
+        // Check from left to right, going down the rows of trees
+        check_visibility(
+            0..height,
+            0..width,
+            trees,
+            &mut visibilities,
+            RangeType::Vertical,
+        );
+        // Check from right to left, going down the rows of trees
+        check_visibility(
+            0..height,
+            (0..width).rev(),
+            trees,
+            &mut visibilities,
+            RangeType::Vertical,
+        );
+        // Check from top to bottom, going across the columns of trees
+        check_visibility(
+            0..width,
+            0..height,
+            trees,
+            &mut visibilities,
+            RangeType::Horizontal,
+        );
+        // Check from bottom to top, going across the columns of trees
+        check_visibility(
+            0..width,
+            (0..height).rev(),
+            trees,
+            &mut visibilities,
+            RangeType::Horizontal,
+        );
+
+        let number_of_trees: usize = visibilities
+            .iter()
+            .map(|l| {
+                l.iter()
+                    .filter(|c| **c)
+                    .count()
+            })
+            .sum();
+
+        Some(number_of_trees)
+    }
+
+    fn part_two(trees: &Self::Input) -> Option<usize> {
+        let height = trees.len() - 1;
+        let width = trees.first()?.len() - 1;
+        let mut highest_score: usize = 0;
+
+        for (y, tree_row) in trees.iter().enumerate() {
+            for (x, _tree) in tree_row.iter().enumerate() {
+                let top = check_vertical_score((0..=y).rev(), x, trees);
+                let bottom = check_vertical_score(y..=height, x, trees);
+                let left = check_horizontal_score((0..=x).rev(), y, trees);
+                let right = check_horizontal_score(x..=width, y, trees);
+
+                let score = top * bottom * left * right;
+                if score > highest_score {
+                    highest_score = score;
+                }
             }
         }
-    }
 
-    Some(highest_score)
+        Some(highest_score)
+    }
 }
 
 fn main() {
-    let input = &advent_of_code::read_file("inputs", 8);
-    advent_of_code::solve!(1, part_one, input);
-    advent_of_code::solve!(2, part_two, input);
+    advent_of_code::solve!(Day08);
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let input = advent_of_code::read_file("examples", 8);
-        assert_eq!(part_one(&input), Some(21));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let input = advent_of_code::read_file("examples", 8);
-        assert_eq!(part_two(&input), Some(8));
-    }
-}
+advent_of_code::example_tests!(Day08);
@@ -1,3 +1,5 @@
+use advent_of_code::parse::parse_field;
+use advent_of_code::{debug, trace, ParseError, Solution};
 use std::collections::HashSet;
 use strum::*;
 
@@ -58,22 +60,16 @@ pub struct Motion {
 }
 
 impl Motion {
-    pub fn new(motion: &str) -> Self {
-        let mut motion = motion.split_whitespace();
-        let direction = motion
-            .next()
-            .unwrap()
-            .parse::<Direction>()
-            .unwrap();
-        let distance = motion
-            .next()
-            .unwrap()
-            .parse()
-            .unwrap();
-        Self {
-            direction,
-            distance,
-        }
+    pub fn new(motion: &str) -> Result<Self, ParseError> {
+        let (direction, distance) = motion
+            .split_once(' ')
+            .ok_or_else(|| {
+                ParseError::new(motion, "expected a direction and a distance, e.g. `R 4`")
+            })?;
+        Ok(Self {
+            direction: parse_field(direction)?,
+            distance: parse_field(distance)?,
+        })
     }
 
     pub fn create_point(&self, point: Point) -> Point {
@@ -98,36 +94,6 @@ impl Motion {
     }
 }
 
-pub fn part_one(input: &str) -> Option<usize> {
-    let motions: Vec<Motion> = input
-        .lines()
-        .map(Motion::new)
-        .collect();
-
-    let mut head_point = Point { x: 0, y: 0 };
-    let mut tail_point = head_point;
-    let mut unique_points: HashSet<Point> = HashSet::new();
-    unique_points.insert(tail_point);
-
-    for motion in motions.iter() {
-        for _step in 0..motion.distance {
-            head_point = motion.create_point(head_point);
-            if !&tail_point.is_touching(&head_point) {
-                let tail_motions = &head_point.compute_tail_motion(tail_point);
-                for tail_motion in tail_motions.iter() {
-                    if tail_motion.distance == 0 {
-                        panic!("Tail falling behind!");
-                    }
-                    tail_point = tail_motion.create_point(tail_point);
-                }
-                unique_points.insert(tail_point);
-            }
-        }
-    }
-
-    Some(unique_points.len())
-}
-
 pub trait Rope {
     fn update_point(&mut self, idx: usize);
 }
@@ -136,7 +102,7 @@ impl Rope for Vec<Point> {
     fn update_point(&mut self, idx: usize) {
         let mut point = self[idx];
         let parent_point = self[idx - 1];
-        println!("Check:  {point:?} ({idx})");
+        trace!("Check:  {point:?} ({idx})");
 
         if !point.is_touching(&parent_point) {
             let motions = &parent_point.compute_tail_motion(point);
@@ -147,56 +113,76 @@ impl Rope for Vec<Point> {
                 point = motion.create_point(point);
             }
             self[idx] = point;
-            println!("Update: {point:?} ({idx}) with {motions:?} toward {parent_point:?}");
+            trace!("Update: {point:?} ({idx}) with {motions:?} toward {parent_point:?}");
         }
     }
 }
 
-pub fn part_two(input: &str) -> Option<usize> {
-    let motions: Vec<Motion> = input
-        .lines()
-        .map(Motion::new)
-        .collect();
-
-    let rope_length = 10;
-    let mut rope: Vec<Point> = vec![Point { x: 0, y: 0 }; rope_length];
-    let mut unique_points: HashSet<Point> = HashSet::new();
-    unique_points.insert(rope[rope_length - 1]);
-
-    for (idx, motion) in motions.iter().enumerate() {
-        println!("Motion: {motion:?} ({idx})");
-        for _step in 0..motion.distance {
-            rope[0] = motion.create_point(rope[0]);
-            println!("Head:   {:?}", rope[0]);
-            for i in 1..rope_length {
-                rope.update_point(i)
+pub struct Day09;
+
+impl Solution for Day09 {
+    const DAY: u8 = 9;
+
+    type Input = Vec<Motion>;
+    type PartOne = usize;
+    type PartTwo = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        input
+            .lines()
+            .map(Motion::new)
+            .collect()
+    }
+
+    fn part_one(motions: &Self::Input) -> Option<usize> {
+        let mut head_point = Point { x: 0, y: 0 };
+        let mut tail_point = head_point;
+        let mut unique_points: HashSet<Point> = HashSet::new();
+        unique_points.insert(tail_point);
+
+        for motion in motions.iter() {
+            for _step in 0..motion.distance {
+                head_point = motion.create_point(head_point);
+                if !&tail_point.is_touching(&head_point) {
+                    let tail_motions = &head_point.compute_tail_motion(tail_point);
+                    for tail_motion in tail_motions.iter() {
+                        if tail_motion.distance == 0 {
+                            panic!("Tail falling behind!");
+                        }
+                        tail_point = tail_motion.create_point(tail_point);
+                    }
+                    unique_points.insert(tail_point);
+                }
             }
-            unique_points.insert(rope[rope_length - 1]);
         }
+
+        Some(unique_points.len())
     }
 
-    Some(unique_points.len())
+    fn part_two(motions: &Self::Input) -> Option<usize> {
+        let rope_length = 10;
+        let mut rope: Vec<Point> = vec![Point { x: 0, y: 0 }; rope_length];
+        let mut unique_points: HashSet<Point> = HashSet::new();
+        unique_points.insert(rope[rope_length - 1]);
+
+        for (idx, motion) in motions.iter().enumerate() {
+            debug!("Motion: {motion:?} ({idx})");
+            for _step in 0..motion.distance {
+                rope[0] = motion.create_point(rope[0]);
+                trace!("Head:   {:?}", rope[0]);
+                for i in 1..rope_length {
+                    rope.update_point(i)
+                }
+                unique_points.insert(rope[rope_length - 1]);
+            }
+        }
+
+        Some(unique_points.len())
+    }
 }
 
 fn main() {
-    let input = &advent_of_code::read_file("inputs", 9);
-    advent_of_code::solve!(1, part_one, input);
-    advent_of_code::solve!(2, part_two, input);
+    advent_of_code::solve!(Day09);
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let input = advent_of_code::read_file("examples", 9);
-        assert_eq!(part_one(&input), Some(13));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let input = advent_of_code::read_file("examples", 9);
-        assert_eq!(part_two(&input), Some(1));
-    }
-}
+advent_of_code::example_tests!(Day09);
@@ -1,135 +1,101 @@
-pub fn part_one(input: &str) -> Option<i32> {
-    let mut instructions: Vec<(i32, i32)> = input
-        .lines()
-        .flat_map(|l| match l {
-            "noop" => vec![0_i32],
-            _ => vec![
-                0,
-                l.split_once(' ')
-                    .unwrap()
-                    .1
-                    .parse::<i32>()
-                    .unwrap(),
-            ],
-        })
-        .map(|e| (e, 0_i32))
-        .collect();
+use advent_of_code::parse::{expect_prefix, parse_field};
+use advent_of_code::{trace, ParseError, Solution};
 
-    let mut total_signal: i32 = 1;
-    for i in instructions.iter_mut() {
-        total_signal += i.0;
-        i.1 = total_signal
-    }
+pub struct Day10;
 
-    // For some reason, my interpreted instructions are offset by +1 from the instructions that
-    // yield the correct answer. My instructions look correct, but the `signal_strength` they
-    // produce is incorrect. Adding an extra starting instruction fixes it though.
-    instructions.insert(0, (0, 1));
+impl Solution for Day10 {
+    const DAY: u8 = 10;
 
-    let signal_strength = instructions
-        .iter()
-        .enumerate()
-        .filter(|&(i, _)| (i as i32 - 19).rem_euclid(40) == 0)
-        .take(6)
-        .map(|(i, e)| e.1 * (i as i32 + 1))
-        .sum();
+    // `(instruction, register)` pairs, one per cycle.
+    type Input = Vec<(i32, i32)>;
+    type PartOne = i32;
+    type PartTwo = String;
 
-    Some(signal_strength)
-}
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        let instructions: Vec<Vec<i32>> = input
+            .lines()
+            .map(|l| match l {
+                "noop" => Ok(vec![0_i32]),
+                _ => Ok(vec![0, parse_field::<i32>(expect_prefix(l, "addx ")?)?]),
+            })
+            .collect::<Result<_, ParseError>>()?;
+
+        let mut instructions: Vec<(i32, i32)> = instructions
+            .into_iter()
+            .flatten()
+            .map(|e| (e, 0_i32))
+            .collect();
+
+        let mut total_signal: i32 = 1;
+        for i in instructions.iter_mut() {
+            total_signal += i.0;
+            i.1 = total_signal
+        }
 
-pub fn part_two(input: &str) -> Option<String> {
-    let mut instructions: Vec<(i32, i32)> = input
-        .lines()
-        .flat_map(|l| match l {
-            "noop" => vec![0_i32],
-            _ => vec![
-                0,
-                l.split_once(' ')
-                    .unwrap()
-                    .1
-                    .parse::<i32>()
-                    .unwrap(),
-            ],
-        })
-        .map(|e| (e, 0_i32))
-        .collect();
+        // For some reason, my interpreted instructions are offset by +1 from the instructions that
+        // yield the correct answer. My instructions look correct, but the `signal_strength` they
+        // produce is incorrect. Adding an extra starting instruction fixes it though.
+        instructions.insert(0, (0, 1));
 
-    let mut total_signal: i32 = 1;
-    for i in instructions.iter_mut() {
-        total_signal += i.0;
-        i.1 = total_signal
+        Ok(instructions)
     }
 
-    instructions.insert(0, (0, 1));
-    let mut crt = String::new();
-    instructions
-        .iter()
-        .enumerate()
-        .for_each(|(cycle, (_instruction, register))| {
-            match (cycle as i32)
-                .rem_euclid(40)
-                .saturating_sub(*register)
-                .abs()
-                .cmp(&1)
-            {
-                std::cmp::Ordering::Greater => {
-                    println!(". {cycle}, {register}");
-                    crt.push('.')
-                }
-                _ => {
-                    println!("# {cycle}, {register}");
-                    crt.push('#')
+    fn part_one(instructions: &Self::Input) -> Option<i32> {
+        let signal_strength = instructions
+            .iter()
+            .enumerate()
+            .filter(|&(i, _)| (i as i32 - 19).rem_euclid(40) == 0)
+            .take(6)
+            .map(|(i, e)| e.1 * (i as i32 + 1))
+            .sum();
+
+        Some(signal_strength)
+    }
+
+    fn part_two(instructions: &Self::Input) -> Option<String> {
+        let mut crt = String::new();
+        instructions
+            .iter()
+            .enumerate()
+            .for_each(|(cycle, (_instruction, register))| {
+                match (cycle as i32)
+                    .rem_euclid(40)
+                    .saturating_sub(*register)
+                    .abs()
+                    .cmp(&1)
+                {
+                    std::cmp::Ordering::Greater => {
+                        trace!(". {cycle}, {register}");
+                        crt.push('.')
+                    }
+                    _ => {
+                        trace!("# {cycle}, {register}");
+                        crt.push('#')
+                    }
                 }
-            }
-        });
+            });
 
-    crt = crt
-        .chars()
-        .enumerate()
-        .flat_map(|(i, c)| {
-            if i != 0 && (i as i32).rem_euclid(40) == 0 {
-                Some('\n')
-            } else {
-                None
-            }
-            .into_iter()
-            .chain(std::iter::once(c))
-        })
-        .collect::<String>();
-    crt.pop();
+        crt = crt
+            .chars()
+            .enumerate()
+            .flat_map(|(i, c)| {
+                if i != 0 && (i as i32).rem_euclid(40) == 0 {
+                    Some('\n')
+                } else {
+                    None
+                }
+                .into_iter()
+                .chain(std::iter::once(c))
+            })
+            .collect::<String>();
+        crt.pop();
 
-    Some(crt)
+        Some(crt)
+    }
 }
 
 fn main() {
-    let input = &advent_of_code::read_file("inputs", 10);
-    advent_of_code::solve!(1, part_one, input);
-    advent_of_code::solve!(2, part_two, input);
+    advent_of_code::solve!(Day10);
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let input = advent_of_code::read_file("examples", 10);
-        assert_eq!(part_one(&input), Some(13140));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let input = advent_of_code::read_file("examples", 10);
-        let output = "##..##..##..##..##..##..##..##..##..##..\n\
-            ###...###...###...###...###...###...###.\n\
-            ####....####....####....####....####....\n\
-            #####.....#####.....#####.....#####.....\n\
-            ######......######......######......####\n\
-            #######.......#######.......#######.....\n"
-            .to_string();
-        let lhs = part_two(&input).unwrap();
-        println!("{lhs}");
-        println!("{output}");
-        assert_eq!(Some(lhs), Some(output));
-    }
-}
+advent_of_code::example_tests!(Day10);
@@ -1,77 +1,93 @@
 #![feature(int_roundings)]
 
+use advent_of_code::parse::{expect_prefix, parse_field};
+use advent_of_code::{debug, trace, ParseError, Solution};
 use itertools::Itertools;
 use std::collections::VecDeque;
-use std::iter::IntoIterator;
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 #[allow(dead_code)]
-pub struct Monkey<'a> {
+pub struct Monkey {
     items: VecDeque<u64>,
-    operation: (&'a str, &'a str),
+    operation: (String, String),
     test: u64,
     test_true: usize,
     test_false: usize,
 }
 
-impl<'a> FromIterator<&'a str> for Monkey<'a> {
-    fn from_iter<I>(iter: I) -> Self
-    where
-        I: IntoIterator<Item = &'a str>,
-    {
-        let mut lines = iter.into_iter();
-        lines.next();
-
-        // I hate this. But first time implementing `FromIterator`, so I'll keep it!
-        Self {
-            items: lines
-                .next()
-                .unwrap()
-                .strip_prefix("  Starting items: ")
-                .unwrap()
-                .split(", ")
-                .into_iter()
-                .map(|c| c.parse().unwrap())
-                .collect::<VecDeque<u64>>(),
-            operation: lines
-                .next()
-                .unwrap()
-                .strip_prefix("  Operation: new = old ")
-                .unwrap()
-                .split_once(' ')
-                .unwrap(),
-            test: lines
-                .next()
-                .unwrap()
-                .strip_prefix("  Test: divisible by ")
-                .unwrap()
-                .parse()
-                .unwrap(),
-            test_true: lines
-                .next()
-                .unwrap()
-                .strip_prefix("    If true: throw to monkey ")
-                .unwrap()
-                .parse()
-                .unwrap(),
-            test_false: lines
-                .next()
-                .unwrap()
-                .strip_prefix("    If false: throw to monkey ")
-                .unwrap()
-                .parse()
-                .unwrap(),
+/// Returns the next line of a monkey's notes, without the expected `prefix`.
+fn next_line<'a>(
+    lines: &mut impl Iterator<Item = &'a str>,
+    notes: &'a str,
+    prefix: &str,
+) -> Result<&'a str, ParseError> {
+    let line = lines.next().ok_or_else(|| {
+        ParseError::new(
+            &notes[notes.len()..],
+            format!("expected {:?}", prefix.trim()),
+        )
+    })?;
+    expect_prefix(line, prefix)
+}
+
+/// Parses the monkey that an item is thrown to, one of the `monkey_count` monkeys.
+fn parse_target(target: &str, monkey_count: usize) -> Result<usize, ParseError> {
+    match parse_field(target)? {
+        monkey if monkey >= monkey_count => Err(ParseError::new(
+            target,
+            format!("expected a monkey from 0 to {}", monkey_count - 1),
+        )),
+        monkey => Ok(monkey),
+    }
+}
+
+impl Monkey {
+    /// Parses the notes on one of `monkey_count` monkeys.
+    pub fn parse(notes: &str, monkey_count: usize) -> Result<Self, ParseError> {
+        let mut lines = notes.lines();
+        next_line(&mut lines, notes, "Monkey ")?;
+
+        let items = next_line(&mut lines, notes, "  Starting items: ")?
+            .split(", ")
+            .map(parse_field)
+            .collect::<Result<VecDeque<u64>, ParseError>>()?;
+
+        let operation = next_line(&mut lines, notes, "  Operation: new = old ")?;
+        let (operator, operand) = operation
+            .split_once(' ')
+            .ok_or_else(|| ParseError::new(operation, "expected an operator and an operand"))?;
+        if operator != "+" && operator != "*" {
+            return Err(ParseError::new(operator, "expected `+` or `*`"));
         }
+        if operand != "old" {
+            parse_field::<u64>(operand)?;
+        }
+
+        let test = next_line(&mut lines, notes, "  Test: divisible by ")?;
+        let test = match parse_field(test)? {
+            0 => return Err(ParseError::new(test, "cannot test divisibility by 0")),
+            test => test,
+        };
+
+        Ok(Self {
+            items,
+            operation: (operator.to_string(), operand.to_string()),
+            test,
+            test_true: parse_target(
+                next_line(&mut lines, notes, "    If true: throw to monkey ")?,
+                monkey_count,
+            )?,
+            test_false: parse_target(
+                next_line(&mut lines, notes, "    If false: throw to monkey ")?,
+                monkey_count,
+            )?,
+        })
     }
 }
 
-pub fn compute_monkey_business(rounds: u32, input: &str, reduce_worry: bool) -> Option<u64> {
-    let mut monkeys: Vec<Monkey> = input
-        .split("\n\n")
-        .into_iter()
-        .map(|t| Monkey::from_iter(t.lines()))
-        .collect();
-    println!("{monkeys:?}");
+pub fn compute_monkey_business(rounds: u32, monkeys: &[Monkey], reduce_worry: bool) -> Option<u64> {
+    let mut monkeys = monkeys.to_vec();
+    debug!("{monkeys:?}");
 
     // Had to look up the math for this part. Essentially boils down to using a common denominator
     // for operations that would otherwise yield numbers that are too big.
@@ -90,12 +106,12 @@ pub fn compute_monkey_business(rounds: u32, input: &str, reduce_worry: bool) ->
             .iter_mut()
             .enumerate()
             .for_each(|(i, monkey)| {
-                println!("\nMonkey {i}, Round {round}");
-                println!("Found items in queue: {:?}", queue[i]);
+                trace!("Monkey {i}, Round {round}");
+                trace!("Found items in queue: {:?}", queue[i]);
                 monkey
                     .items
                     .append(&mut queue[i]);
-                println!(
+                trace!(
                     "Moved items in queue to monkey: {:?}, {queue:?}",
                     monkey.items
                 );
@@ -107,7 +123,7 @@ pub fn compute_monkey_business(rounds: u32, input: &str, reduce_worry: bool) ->
 
                     inspections[i] += 1;
 
-                    match monkey.operation.0 {
+                    match monkey.operation.0.as_str() {
                         "+" if monkey.operation.1 == "old" => item *= 2,
                         "+" => {
                             item += monkey
@@ -137,14 +153,14 @@ pub fn compute_monkey_business(rounds: u32, input: &str, reduce_worry: bool) ->
                         true => queue[monkey.test_true].push_back(item),
                         false => queue[monkey.test_false].push_back(item),
                     }
-                    println!("Added items to queue: {queue:?}");
+                    trace!("Added items to queue: {queue:?}");
                 }
             });
         queue
             .iter_mut()
             .zip(monkeys.iter_mut())
             .for_each(|(q, m)| m.items.append(q));
-        println!("{round}, {monkeys:?}");
+        debug!("{round}, {monkeys:?}");
     });
 
     inspections.sort();
@@ -158,34 +174,64 @@ pub fn compute_monkey_business(rounds: u32, input: &str, reduce_worry: bool) ->
     Some(monkey_business)
 }
 
-pub fn part_one(input: &str) -> Option<u64> {
-    compute_monkey_business(20, input, true)
-}
+pub struct Day11;
+
+impl Solution for Day11 {
+    const DAY: u8 = 11;
 
-pub fn part_two(input: &str) -> Option<u64> {
-    // Performance on this is terrible. Will analyze other solutions to learn from this puzzle.
-    compute_monkey_business(10_000, input, false)
+    type Input = Vec<Monkey>;
+    type PartOne = u64;
+    type PartTwo = u64;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        let notes: Vec<&str> = input.split("\n\n").collect();
+        notes
+            .iter()
+            .map(|notes_on_monkey| Monkey::parse(notes_on_monkey, notes.len()))
+            .collect()
+    }
+
+    fn part_one(monkeys: &Self::Input) -> Option<u64> {
+        compute_monkey_business(20, monkeys, true)
+    }
+
+    fn part_two(monkeys: &Self::Input) -> Option<u64> {
+        // Performance on this is terrible. Will analyze other solutions to learn from this puzzle.
+        compute_monkey_business(10_000, monkeys, false)
+    }
 }
 
 fn main() {
-    let input = &advent_of_code::read_file("inputs", 11);
-    advent_of_code::solve!(1, part_one, input);
-    advent_of_code::solve!(2, part_two, input);
+    advent_of_code::solve!(Day11);
 }
 
+advent_of_code::example_tests!(Day11);
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    #[test]
-    fn test_part_one() {
-        let input = advent_of_code::read_file("examples", 11);
-        assert_eq!(part_one(&input), Some(10605));
-    }
+    const NOTES: &str = "Monkey 0:\n  Starting items: 79, 98\n  Operation: new = old * 19\n  Test: divisible by 23\n    If true: throw to monkey 1\n    If false: throw to monkey 2";
 
     #[test]
-    fn test_part_two() {
-        let input = advent_of_code::read_file("examples", 11);
-        assert_eq!(part_two(&input), Some(2713310158));
+    fn test_parse_rejects_unknown_monkeys() {
+        assert!(Monkey::parse(NOTES, 3).is_ok());
+
+        let error = Day11::parse(NOTES)
+            .unwrap_err()
+            .locate(NOTES);
+        assert_eq!(
+            error.summary(),
+            "line 5, column 30: expected a monkey from 0 to 0"
+        );
+
+        let notes = NOTES.replace("by 23", "by 0");
+        let error = Monkey::parse(&notes, 3)
+            .unwrap_err()
+            .locate(&notes);
+        assert_eq!(
+            error.summary(),
+            "line 4, column 22: cannot test divisibility by 0"
+        );
     }
 }
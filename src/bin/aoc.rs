@@ -0,0 +1,263 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+//! `cargo aoc <command>`: one entry point for all commands of this repository.
+//!
+//! The commands themselves live in their own binaries, so that `scaffold` and `download` keep
+//! working while a solution does not compile. This binary picks the binary and runs it through
+//! cargo, passing on the arguments it does not need itself.
+use advent_of_code::cli::{self, Exit};
+use advent_of_code::{input, COMPILE_ERRORS_VAR};
+use std::env;
+use std::ffi::OsString;
+use std::fs;
+use std::process::{self, Command, Stdio};
+
+/// The binary of the day in `args`, which is given as a number or as the name of its binary,
+/// e.g. `2021-01`. The day comes first, so that it is not mistaken for the value of an option.
+fn take_day(name: &str, args: &mut Vec<OsString>) -> Option<String> {
+    let day = args
+        .first()
+        .filter(|arg| {
+            !arg.to_string_lossy()
+                .starts_with('-')
+        })?
+        .to_string_lossy()
+        .into_owned();
+    args.remove(0);
+    match (day.parse::<u8>(), input::parse_bin_name(&day)) {
+        (Ok(number), _) => Some(input::bin_name(cli::check_day(name, number))),
+        (Err(_), Some((_, number))) => {
+            cli::check_day(name, number);
+            Some(day)
+        }
+        _ => cli::usage_error(
+            name,
+            format!("invalid day \"{day}\", expected a number from 1 to 25"),
+        ),
+    }
+}
+
+/// How cargo is asked to run a command.
+struct Invocation {
+    /// The arguments to cargo.
+    cargo: Vec<OsString>,
+    /// The options that select the build, `--release` and `--features`.
+    build: Vec<OsString>,
+}
+
+/// How cargo runs the command `name` with `args`.
+fn invocation(name: &str, mut args: pico_args::Arguments) -> Result<Invocation, pico_args::Error> {
+    // Days of other years have binaries of their own, so `--year` picks the binary of `solve`,
+    // `bench` and `test`. The other commands take it themselves.
+    if matches!(name, "solve" | "bench" | "test") {
+        if let Some(year) = args.opt_value_from_str(["-y", "--year"])? {
+            input::set_year(year);
+        }
+    }
+    let mut build: Vec<OsString> = vec![];
+    if matches!(name, "solve" | "all" | "bench" | "submit") {
+        // `bench` always measures an optimized build, and `all` does unless asked for a debug one.
+        let release = args.contains("--release");
+        let debug = name == "all" && args.contains("--debug");
+        if release || name == "bench" || (name == "all" && !debug) {
+            build.push("--release".into());
+        }
+        if let Some(features) = args.opt_value_from_str::<_, String>("--features")? {
+            build.extend(["--features".into(), features.into()]);
+        }
+    }
+    let mut rest = args.finish();
+
+    let manifest = input::project_dir().join("Cargo.toml");
+    let mut cargo: Vec<OsString> = vec![];
+    if name == "test" {
+        cargo.push("test".into());
+        if let Some(bin) = take_day(name, &mut rest) {
+            cargo.extend(["--bin".into(), bin.into()]);
+        }
+        cargo.extend(["--manifest-path".into(), manifest.into()]);
+        cargo.extend(rest);
+        return Ok(Invocation { cargo, build });
+    }
+
+    let bin = match name {
+        "solve" | "bench" => take_day(name, &mut rest).unwrap_or_else(|| {
+            cli::usage_error(
+                name,
+                format!("need to specify a day, e.g. `cargo aoc {name} 7`"),
+            )
+        }),
+        "all" => "advent_of_code".to_string(),
+        _ => name.to_string(),
+    };
+    cargo.extend(["run".into(), "--bin".into(), bin.into()]);
+    cargo.extend(build.iter().cloned());
+    cargo.extend(["--manifest-path".into(), manifest.into(), "--".into()]);
+    if name == "bench" {
+        cargo.push("--bench".into());
+    }
+    cargo.extend(rest);
+    Ok(Invocation { cargo, build })
+}
+
+/// Whether `var` is one of the variables that cargo sets for the binary it runs. Build scripts
+/// of dependencies rerun when they change, so they are not passed on to the nested cargo.
+fn set_by_cargo_run(var: &str) -> bool {
+    const PREFIXES: [&str; 5] = [
+        "CARGO_PKG_",
+        "CARGO_MANIFEST_",
+        "CARGO_CRATE_",
+        "CARGO_BIN_",
+        "CARGO_PRIMARY_PACKAGE",
+    ];
+    var == "OUT_DIR"
+        || PREFIXES
+            .iter()
+            .any(|prefix| var.starts_with(prefix))
+}
+
+/// A cargo command that builds this repository as if it was run directly.
+fn cargo_command() -> Command {
+    let mut command = Command::new(env::var_os("CARGO").unwrap_or_else(|| "cargo".into()));
+    for (var, _) in env::vars_os() {
+        if set_by_cargo_run(&var.to_string_lossy()) {
+            command.env_remove(var);
+        }
+    }
+    command
+}
+
+/// Checks every day on its own with the options in `build`, and returns the first error of each
+/// day that does not compile as `<binary>\t<error>` lines. The runner leaves these days out, see
+/// [`COMPILE_ERRORS_VAR`], so that one broken day does not keep the others from running.
+fn compile_errors(build: &[OsString]) -> String {
+    let project_dir = input::project_dir();
+    let mut days: Vec<String> = fs::read_dir(
+        project_dir
+            .join("src")
+            .join("bin"),
+    )
+    .into_iter()
+    .flatten()
+    .flatten()
+    .filter_map(|entry| {
+        let stem = entry
+            .file_name()
+            .to_str()?
+            .strip_suffix(".rs")?
+            .to_string();
+        input::parse_bin_name(&stem).map(|_| stem)
+    })
+    .collect();
+    days.sort();
+
+    let mut command = cargo_command();
+    command
+        .args([
+            "check",
+            "--keep-going",
+            "--message-format",
+            "short",
+            "--color",
+            "never",
+        ])
+        .args(build)
+        .arg("--manifest-path")
+        .arg(project_dir.join("Cargo.toml"))
+        .current_dir(&project_dir)
+        .env_remove(COMPILE_ERRORS_VAR)
+        .stdout(Stdio::null());
+    for day in &days {
+        command.args(["--bin", day]);
+    }
+    // If the check itself fails, running the command reports the problem.
+    let stderr = match command.output() {
+        Ok(output) if !output.status.success() => {
+            String::from_utf8_lossy(&output.stderr).into_owned()
+        }
+        _ => return String::new(),
+    };
+
+    days.iter()
+        .filter_map(|day| {
+            let file = format!("src/bin/{day}.rs:");
+            let error = stderr
+                .lines()
+                .find(|line| line.starts_with(&file) && line.contains(": error"))?;
+            Some(format!("{day}\t{error}\n"))
+        })
+        .collect()
+}
+
+fn main() {
+    let mut args: Vec<OsString> = env::args_os()
+        .skip(1)
+        .collect();
+    // `cargo aoc help <command>` is `cargo aoc <command> --help`.
+    if args
+        .first()
+        .is_some_and(|arg| arg == "help")
+        && args.len() > 1
+    {
+        args = vec![args[1].clone(), "--help".into()];
+    }
+
+    let name = match args.first().map(|arg| {
+        arg.to_string_lossy()
+            .into_owned()
+    }) {
+        None => cli::fail(Exit::Usage, cli::help()),
+        Some(name) if matches!(name.as_str(), "-h" | "--help" | "help") => {
+            println!("{}", cli::help());
+            Exit::Success.exit();
+        }
+        Some(name) if cli::command(&name).is_none() => cli::fail(
+            Exit::Usage,
+            format!("error: there is no command \"{name}\"\n\n{}", cli::help()),
+        ),
+        Some(name) => name,
+    };
+
+    // Arguments after `--` are passed on untouched, so a `--help` among them is not for us.
+    let mut ours = args.split_off(1);
+    let passed_on = match ours
+        .iter()
+        .position(|arg| arg == "--")
+    {
+        Some(index) => ours.split_off(index),
+        None => vec![],
+    };
+    let mut parsed = pico_args::Arguments::from_vec(ours);
+    cli::handle_help(&mut parsed, &name);
+
+    let Invocation { mut cargo, build } =
+        invocation(&name, parsed).unwrap_or_else(|e| cli::usage_error(&name, e));
+    // `cargo test` takes the `--` itself, the binaries already got one.
+    let skip = usize::from(name != "test" && !passed_on.is_empty());
+    cargo.extend(
+        passed_on
+            .into_iter()
+            .skip(skip),
+    );
+
+    let mut command = cargo_command();
+    // `all` and `submit` compile every day in, so days that do not compile are left out.
+    if matches!(name.as_str(), "all" | "submit") {
+        match compile_errors(&build) {
+            errors if errors.is_empty() => command.env_remove(COMPILE_ERRORS_VAR),
+            errors => command.env(COMPILE_ERRORS_VAR, errors),
+        };
+    }
+    let status = command
+        .args(&cargo)
+        .status()
+        .unwrap_or_else(|e| cli::fail(Exit::Environment, format!("Failed to run cargo: {e}")));
+    // The exit code of the command is ours. One that was killed by a signal has none.
+    process::exit(
+        status
+            .code()
+            .unwrap_or(Exit::Failure as i32),
+    );
+}
@@ -0,0 +1,347 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use advent_of_code::args::SiteArgs;
+use advent_of_code::checksum::{self, Check};
+use advent_of_code::cli::{self, Exit};
+use advent_of_code::client::{self, ClientError};
+use advent_of_code::examples::Example;
+use advent_of_code::{input, layout};
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+struct Args {
+    offline: bool,
+    site: SiteArgs,
+}
+
+fn parse_args() -> Result<Args, pico_args::Error> {
+    let mut args = pico_args::Arguments::from_env();
+    cli::handle_help(&mut args, "doctor");
+    let parsed = Args {
+        offline: args.contains("--offline"),
+        site: SiteArgs::parse(&mut args)?,
+    };
+    cli::finish(args)?;
+    Ok(parsed)
+}
+
+/// The result of one check.
+#[derive(Debug, PartialEq, Eq)]
+enum Outcome {
+    Pass(String),
+    /// Something is wrong, and this is how to fix it.
+    Fail(String, String),
+    /// Worth knowing, but nothing needs to be done.
+    Note(String),
+}
+
+fn fail(problem: impl Into<String>, fix: impl Into<String>) -> Outcome {
+    Outcome::Fail(problem.into(), fix.into())
+}
+
+/// The options that select the current year, as they are appended to a command.
+fn year_suffix() -> String {
+    input::year_args()
+        .iter()
+        .map(|arg| format!(" {arg}"))
+        .collect()
+}
+
+/// The days of the current year that have a solution in `src/bin`, sorted.
+fn solved_days() -> Vec<u8> {
+    let year = input::year();
+    let mut days: Vec<u8> = fs::read_dir(
+        input::project_dir()
+            .join("src")
+            .join("bin"),
+    )
+    .into_iter()
+    .flatten()
+    .flatten()
+    .filter_map(|entry| {
+        let name = entry.file_name();
+        let (bin_year, day) = input::parse_bin_name(
+            name.to_str()?
+                .strip_suffix(".rs")?,
+        )?;
+        (bin_year.unwrap_or_else(input::default_year) == year).then_some(day)
+    })
+    .collect();
+    days.sort();
+    days
+}
+
+/// The unstable features that solutions enable, e.g. "`int_roundings` (day 11)".
+fn unstable_features() -> Vec<String> {
+    solved_days()
+        .into_iter()
+        .flat_map(|day| {
+            let source = fs::read_to_string(
+                input::project_dir()
+                    .join("src")
+                    .join("bin")
+                    .join(format!("{}.rs", input::bin_name(day))),
+            );
+            source
+                .unwrap_or_default()
+                .lines()
+                .filter_map(|line| {
+                    line.trim()
+                        .strip_prefix("#![feature(")?
+                        .strip_suffix(")]")
+                        .map(str::to_string)
+                })
+                .map(|feature| format!("`{feature}` (day {day})"))
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+fn check_toolchain() -> Outcome {
+    let features = unstable_features();
+    let needs = match features.len() {
+        0 => "rust-toolchain.toml asks for one".to_string(),
+        1 => format!("the unstable feature {} needs one", features[0]),
+        _ => format!("the unstable features {} need one", features.join(", ")),
+    };
+    let install = "Run `rustup toolchain install nightly`, rust-toolchain.toml then selects it";
+
+    // Run from the repository, so that rustup picks the toolchain that rust-toolchain.toml asks for.
+    let output = Command::new("rustc")
+        .arg("--version")
+        .current_dir(input::project_dir())
+        .output();
+    match output {
+        Ok(output) if output.status.success() => {
+            let version = String::from_utf8_lossy(&output.stdout)
+                .trim()
+                .to_string();
+            match version.contains("nightly") {
+                true => Outcome::Pass(format!("{version} is a nightly toolchain, as {needs}")),
+                false => fail(format!("{version} is not a nightly toolchain, but {needs}"), install),
+            }
+        }
+        Ok(output) => fail(
+            format!("`rustc --version` failed: {}", String::from_utf8_lossy(&output.stderr).trim()),
+            install,
+        ),
+        Err(e) => fail(
+            format!("rustc could not be run: {e}"),
+            "Install Rust with rustup from https://rustup.rs, then run `rustup toolchain install nightly`",
+        ),
+    }
+}
+
+fn check_session(args: &Args) -> Outcome {
+    let session_path = client::session_paths()
+        .first()
+        .map_or_else(
+            || "~/.adventofcode.session".to_string(),
+            |path| path.display().to_string(),
+        );
+    let client = match args.site.client() {
+        Ok(client) => client,
+        Err(ClientError::NoSession) => {
+            return fail(
+                "No session cookie found",
+                format!(
+                    "Copy the `session` cookie of adventofcode.com to \"{session_path}\" or set {}, see \"Set up your session cookie\" in the README",
+                    client::SESSION_VAR
+                ),
+            )
+        }
+        Err(e) => return fail(format!("Cannot talk to the website: {e}"), "See \"Set up your session cookie\" in the README"),
+    };
+    if args.offline {
+        return Outcome::Note(
+            "Found a session cookie, but did not check it with the website because of --offline."
+                .to_string(),
+        );
+    }
+
+    let year = args.site.year();
+    match client.user(year) {
+        Ok(user) => Outcome::Pass(format!("{} accepts the session cookie of {user}", client.base_url())),
+        Err(ClientError::SessionExpired) => fail(
+            format!("{} rejected the session cookie, it has probably expired", client.base_url()),
+            format!(
+                "Log in to the website again and copy the new `session` cookie to \"{session_path}\", or to {} if you set it",
+                client::SESSION_VAR
+            ),
+        ),
+        Err(ClientError::Locked(_)) => Outcome::Note(format!(
+            "Could not check the session cookie, the first puzzle of {year} is not unlocked yet."
+        )),
+        Err(e) => fail(
+            format!("Could not check the session cookie with {}: {e}", client.base_url()),
+            "Check your connection and the base_url in aoc.toml, or pass --offline",
+        ),
+    }
+}
+
+/// Checks the examples of `day` in `dir`.
+fn check_examples(dir: &Path, day: u8) -> Outcome {
+    let fix = format!(
+        "Paste the example from the puzzle into \"{}\", or run `cargo aoc scaffold {day}{} --fetch`",
+        dir.join(format!("{day:02}.txt")).display(),
+        year_suffix()
+    );
+    let paths = layout::example_files(dir, day);
+    if paths.is_empty() {
+        return fail(format!("Day {day:02} has no example"), fix);
+    }
+
+    let mut with_answers = 0;
+    for path in &paths {
+        match Example::read(path) {
+            Ok(example) if example.input().trim().is_empty() => {
+                return fail(format!("Day {day:02}: \"{}\" is empty", path.display()), fix)
+            }
+            Ok(example) => with_answers += usize::from(example.answers.part_one.is_some() || example.answers.part_two.is_some()),
+            Err(e) => {
+                return fail(
+                    format!("Day {day:02}: {e}"),
+                    "Fix the front matter, see \"Run all solutions against the example input\" in the README",
+                )
+            }
+        }
+    }
+    match with_answers {
+        0 => Outcome::Note(format!(
+            "Day {day:02} has {} example(s) without expected answers, so its example tests check nothing.",
+            paths.len()
+        )),
+        _ => Outcome::Pass(format!("Day {day:02} has {} example(s), {with_answers} with expected answers", paths.len())),
+    }
+}
+
+/// Checks the input of `day` in `dir`.
+fn check_input(dir: &Path, day: u8) -> Outcome {
+    let path = dir.join(format!("{day:02}.txt"));
+    let download = format!("cargo aoc download {day}{}", year_suffix());
+    match checksum::check(&path) {
+        Ok(Check::Missing) => fail(
+            format!("Day {day:02} has no input at \"{}\"", path.display()),
+            format!("Run `{download}`"),
+        ),
+        Ok(_) if fs::metadata(&path).is_ok_and(|metadata| metadata.len() == 0) => fail(
+            format!("Day {day:02}: \"{}\" is empty", path.display()),
+            format!("Run `{download}`"),
+        ),
+        Ok(check) if check.failed() => fail(
+            format!("Day {day:02}: \"{}\" is {check}", path.display()),
+            format!("Run `{download} --force` to download it again"),
+        ),
+        Ok(check) => Outcome::Pass(format!("Day {day:02}: \"{}\" is {check}", path.display())),
+        Err(e) => fail(
+            format!("Day {day:02}: could not read \"{}\": {e}", path.display()),
+            "Check the permissions of the file",
+        ),
+    }
+}
+
+/// Runs all checks, in the order they are reported.
+fn check(args: &Args) -> Vec<Outcome> {
+    let mut outcomes = vec![check_toolchain(), check_session(args)];
+    let days = solved_days();
+    if days.is_empty() {
+        outcomes.push(Outcome::Note(format!(
+            "There are no solutions for {} yet, so no examples or inputs were checked.",
+            input::year()
+        )));
+    }
+    let (examples, inputs) = (input::folder_dir("examples"), input::folder_dir("inputs"));
+    for day in days {
+        outcomes.push(check_examples(&examples, day));
+        outcomes.push(check_input(&inputs, day));
+    }
+    outcomes
+}
+
+/// Prints the outcomes and returns how many checks failed.
+fn report(outcomes: &[Outcome]) -> usize {
+    let mut failures = 0;
+    for outcome in outcomes {
+        match outcome {
+            Outcome::Pass(message) => println!("✔ {message}"),
+            Outcome::Note(message) => println!("· {message}"),
+            Outcome::Fail(problem, fix) => {
+                println!("✘ {problem}\n  Fix: {fix}");
+                failures += 1;
+            }
+        }
+    }
+    failures
+}
+
+fn main() {
+    let args = parse_args().unwrap_or_else(|e| cli::usage_error("doctor", e));
+    input::set_year(args.site.year());
+
+    let failures = report(&check(&args));
+    println!("---");
+    match failures {
+        0 => println!("🎄 Everything is set up."),
+        _ => {
+            println!("{failures} check(s) failed, see the fixes above.");
+            Exit::Failure.exit();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn fixtures(folder: &str) -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("src")
+            .join("fixtures")
+            .join("doctor")
+            .join(folder)
+    }
+
+    #[test]
+    fn test_check_examples() {
+        let dir = fixtures("examples");
+        assert_eq!(
+            check_examples(&dir, 1),
+            Outcome::Pass("Day 01 has 2 example(s), 1 with expected answers".to_string())
+        );
+        assert!(
+            matches!(check_examples(&dir, 2), Outcome::Fail(problem, _) if problem.ends_with("02.txt\" is empty"))
+        );
+        assert!(
+            matches!(check_examples(&dir, 3), Outcome::Note(note) if note.contains("without expected answers"))
+        );
+        assert!(
+            matches!(check_examples(&dir, 4), Outcome::Fail(_, fix) if fix.starts_with("Fix the front matter"))
+        );
+        assert!(
+            matches!(check_examples(&dir, 5), Outcome::Fail(problem, fix)
+            if problem == "Day 05 has no example" && fix.contains("cargo aoc scaffold 5"))
+        );
+    }
+
+    #[test]
+    fn test_check_input() {
+        let dir = fixtures("inputs");
+        assert!(
+            matches!(check_input(&dir, 1), Outcome::Pass(message) if message.ends_with("unchanged since download"))
+        );
+        assert!(matches!(check_input(&dir, 2), Outcome::Fail(problem, fix)
+            if problem.contains("edited or truncated") && fix == "Run `cargo aoc download 2 --force` to download it again"));
+        assert!(
+            matches!(check_input(&dir, 3), Outcome::Pass(message) if message.ends_with("without a recorded checksum"))
+        );
+        assert!(
+            matches!(check_input(&dir, 4), Outcome::Fail(problem, _) if problem.ends_with("04.txt\" is empty"))
+        );
+        assert!(matches!(check_input(&dir, 5), Outcome::Fail(problem, fix)
+            if problem.starts_with("Day 05 has no input") && fix == "Run `cargo aoc download 5`"));
+    }
+}
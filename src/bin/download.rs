@@ -2,104 +2,86 @@
  * This file contains template code.
  * There is no need to edit this file unless you want to change template functionality.
  */
-use std::io::Write;
-use std::path::PathBuf;
-use std::{env::temp_dir, io, process::Command};
-use std::{fs, process};
+use advent_of_code::args::SiteArgs;
+use advent_of_code::cli::{self, Exit};
+use advent_of_code::{checksum, input, puzzle};
+use std::fs;
 
 struct Args {
     day: u8,
-    year: Option<i16>,
+    puzzle: bool,
+    force: bool,
+    site: SiteArgs,
 }
 
 fn parse_args() -> Result<Args, pico_args::Error> {
     let mut args = pico_args::Arguments::from_env();
-    Ok(Args {
+    cli::handle_help(&mut args, "download");
+    let parsed = Args {
+        puzzle: args.contains("--puzzle"),
+        force: args.contains("--force"),
+        site: SiteArgs::parse(&mut args)?,
         day: args.free_from_str()?,
-        year: args.opt_value_from_str(["-y", "--year"])?,
-    })
-}
-
-fn remove_file(path: &PathBuf) {
-    #[allow(unused_must_use)]
-    {
-        fs::remove_file(path);
-    }
-}
-
-fn exit_with_status(status: i32, path: &PathBuf) -> ! {
-    remove_file(path);
-    process::exit(status);
+    };
+    cli::finish(args)?;
+    Ok(parsed)
 }
 
 fn main() {
-    // acquire a temp file path to write aoc-cli output to.
-    // aoc-cli expects this file not to be present - delete just in case.
-    let mut tmp_file_path = temp_dir();
-    tmp_file_path.push("aoc_input_tmp");
-    remove_file(&tmp_file_path);
+    let args = parse_args().unwrap_or_else(|e| cli::usage_error("download", e));
+    cli::check_day("download", args.day);
 
-    let args = match parse_args() {
-        Ok(args) => args,
-        Err(e) => {
-            eprintln!("Failed to process arguments: {e}");
-            exit_with_status(1, &tmp_file_path);
-        }
-    };
+    let year = args.site.year();
+    input::set_year(year);
+    let input_path = input::file_path("inputs", args.day);
+    let exists = fs::metadata(&input_path).is_ok_and(|metadata| metadata.len() > 0);
 
-    let day_padded = format!("{:02}", args.day);
-    let input_path = format!("src/inputs/{day_padded}.txt");
-
-    // check if aoc binary exists and is callable.
-    if Command::new("aoc").arg("-V").output().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
-        exit_with_status(1, &tmp_file_path);
-    }
-
-    let mut cmd_args = vec![];
-
-    if let Some(year) = args.year {
-        cmd_args.push("--year".into());
-        cmd_args.push(year.to_string());
+    // Refuse before looking for a session, which is not needed to find out that there is nothing to do.
+    if exists && !args.force && !args.puzzle {
+        cli::fail(Exit::Failure, format!("Input \"{}\" already exists, pass --force to overwrite it.", input_path.display()));
     }
 
-    cmd_args.append(&mut vec![
-        "--input-file".into(),
-        tmp_file_path.to_string_lossy().to_string(),
-        "--day".into(),
-        args.day.to_string(),
-        "download".into(),
-    ]);
+    let client = args
+        .site
+        .client()
+        .unwrap_or_else(|e| cli::fail(Exit::Environment, format!("Failed to download input: {e}")));
 
-    println!("Downloading input with >aoc {}", cmd_args.join(" "));
+    if exists && !args.force {
+        println!("Keeping existing input \"{}\", pass --force to download it again.", input_path.display());
+    } else {
+        println!("Downloading input for day {}, {year} from {}...", args.day, client.base_url());
+        let contents = client
+            .input(year, args.day)
+            .unwrap_or_else(|e| cli::fail(Exit::Environment, format!("Failed to download input: {e}")));
 
-    match Command::new("aoc").args(cmd_args).output() {
-        Ok(cmd_output) => {
-            io::stdout()
-                .write_all(&cmd_output.stdout)
-                .expect("could not write cmd stdout to pipe.");
-            io::stderr()
-                .write_all(&cmd_output.stderr)
-                .expect("could not write cmd stderr to pipe.");
-            if !cmd_output.status.success() {
-                exit_with_status(1, &tmp_file_path);
+        // Read the input back so that a write that went wrong is noticed now, not while solving.
+        let saved = input::write_file(&input_path, &contents)
+            .and_then(|_| checksum::record(&input_path, &contents))
+            .and_then(|_| checksum::check(&input_path));
+        match saved {
+            Ok(checksum::Check::Unchanged) => {
+                println!("---");
+                println!("🎄 Successfully wrote input to \"{}\".", input_path.display());
             }
-        }
-        Err(e) => {
-            eprintln!("failed to spawn aoc-cli: {e}");
-            exit_with_status(1, &tmp_file_path);
+            Ok(check) => cli::fail(Exit::Environment, format!("Failed to save input: \"{}\" is {check}", input_path.display())),
+            Err(e) => cli::fail(Exit::Environment, format!("Failed to save input: {e}")),
         }
     }
 
-    match fs::copy(&tmp_file_path, &input_path) {
-        Ok(_) => {
-            println!("---");
-            println!("🎄 Successfully wrote input to \"{}\".", &input_path);
-            exit_with_status(0, &tmp_file_path);
-        }
-        Err(e) => {
-            eprintln!("could not copy downloaded input to input file: {e}");
-            exit_with_status(1, &tmp_file_path);
+    if args.puzzle {
+        println!("Downloading puzzle description for day {}...", args.day);
+        let markdown = match client.puzzle(year, args.day) {
+            Ok(page) => puzzle::to_markdown(&page),
+            Err(e) => cli::fail(Exit::Environment, format!("Failed to download puzzle description: {e}")),
+        };
+        let Some(markdown) = markdown else {
+            cli::fail(Exit::Environment, "Failed to download puzzle description: the page does not contain one.");
+        };
+
+        let puzzle_path = puzzle::file_path(args.day);
+        match input::write_file(&puzzle_path, &markdown) {
+            Ok(_) => println!("🎄 Successfully wrote puzzle description to \"{}\".", puzzle_path.display()),
+            Err(e) => cli::fail(Exit::Environment, format!("Failed to save puzzle description: {e}")),
         }
     }
 }
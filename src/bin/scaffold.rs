@@ -2,113 +2,206 @@
  * This file contains template code.
  * There is no need to edit this file unless you want to change template functionality.
  */
+use advent_of_code::args::SiteArgs;
+use advent_of_code::cli::{self, Exit};
+use advent_of_code::examples::Example;
+use advent_of_code::template::{self, Vars};
+use advent_of_code::{client, config, input, puzzle};
 use std::{
-    fs::{File, OpenOptions},
+    fs::{self, OpenOptions},
     io::Write,
-    process,
+    path::Path,
 };
 
-const MODULE_TEMPLATE: &str = r###"pub fn part_one(input: &str) -> Option<u32> {
-    None
+struct Args {
+    day: u8,
+    template: String,
+    title: Option<String>,
+    fetch: bool,
+    dry_run: bool,
+    site: SiteArgs,
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
-    None
+fn parse_args() -> Result<Args, pico_args::Error> {
+    let mut args = pico_args::Arguments::from_env();
+    cli::handle_help(&mut args, "scaffold");
+    let parsed = Args {
+        template: args
+            .opt_value_from_str("--template")?
+            .unwrap_or_else(|| template::DEFAULT.to_string()),
+        title: args.opt_value_from_str("--title")?,
+        fetch: args.contains("--fetch"),
+        dry_run: args.contains("--dry-run"),
+        site: SiteArgs::parse(&mut args)?,
+        day: args.free_from_str()?,
+    };
+    cli::finish(args)?;
+    Ok(parsed)
 }
 
-fn main() {
-    let input = &advent_of_code::read_file("inputs", DAY);
-    advent_of_code::solve!(1, part_one, input);
-    advent_of_code::solve!(2, part_two, input);
+/// The puzzle title from `--title`, the fetched puzzle page, or the downloaded puzzle description.
+fn title(args: &Args, page: Option<&str>) -> String {
+    args.title
+        .clone()
+        .or_else(|| puzzle::title(&puzzle::to_markdown(page?)?))
+        .or_else(|| {
+            let markdown = fs::read_to_string(puzzle::file_path(args.day)).ok()?;
+            puzzle::title(&markdown)
+        })
+        .unwrap_or_else(|| "Untitled".to_string())
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let input = advent_of_code::read_file("examples", DAY);
-        assert_eq!(part_one(&input), None);
-    }
+/// The solution module for `args`, rendered from the selected template.
+fn render_module(args: &Args, page: Option<&str>) -> String {
+    let vars = Vars {
+        day: args.day,
+        year: args.site.year(),
+        title: title(args, page),
+    };
+    template::load(&args.template)
+        .and_then(|t| template::render(&t, &vars))
+        .unwrap_or_else(|e| cli::fail(Exit::Environment, format!("Failed to render template \"{}\": {e}", args.template)))
+}
 
-    #[test]
-    fn test_part_two() {
-        let input = advent_of_code::read_file("examples", DAY);
-        assert_eq!(part_two(&input), None);
-    }
+/// Downloads the puzzle page for `--fetch`.
+fn fetch_page(args: &Args) -> String {
+    let page = args.site.client().and_then(|client| {
+        println!("Fetching puzzle page for day {}, {} from {}...", args.day, args.site.year(), client.base_url());
+        client.puzzle(args.site.year(), args.day)
+    });
+    page.unwrap_or_else(|e| cli::fail(Exit::Environment, format!("Failed to fetch puzzle page: {e}")))
 }
-"###;
 
-fn parse_args() -> Result<u8, pico_args::Error> {
-    let mut args = pico_args::Arguments::from_env();
-    args.free_from_str()
+/// The example for `--fetch`, with the expected answers it could find.
+fn fetched_example(page: &str) -> Option<Example> {
+    let example = puzzle::example(page);
+    match &example {
+        Some(example) => {
+            let answers = [("one", &example.answers.part_one), ("two", &example.answers.part_two)];
+            for (part, answer) in answers {
+                if let Some(answer) = answer {
+                    println!("Guessed {answer} as the expected answer to part {part}, check it against the puzzle.");
+                }
+            }
+        }
+        None => eprintln!("The puzzle page has no example, creating an empty example file instead."),
+    }
+    example
 }
 
-fn safe_create_file(path: &str) -> Result<File, std::io::Error> {
-    OpenOptions::new().write(true).create_new(true).open(path)
+/// Creates the file at `path` with `contents`, failing if it already exists.
+fn create_file(path: &Path, contents: &str) -> Result<(), std::io::Error> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let mut file = OpenOptions::new().write(true).create_new(true).open(path)?;
+    file.write_all(contents.as_bytes())
 }
 
-fn create_file(path: &str) -> Result<File, std::io::Error> {
-    OpenOptions::new().write(true).create(true).open(path)
+/// Writes the default year into `aoc.toml` if it has none yet, so that the flat layout keeps
+/// belonging to the year it was started in. Runs before anything reads the configuration.
+fn set_default_year(args: &Args) {
+    let mut config = match config::read() {
+        Ok(config) if config.year.is_none() => config,
+        _ => return,
+    };
+    let year = args.site.year.unwrap_or_else(client::latest_year);
+    if args.dry_run {
+        println!("Would set the default year to {year} in \"{}\"", config::config_path().display());
+        config.year = Some(year);
+        config::init(config);
+        return;
+    }
+    match config::write_year(year) {
+        Ok(_) => println!("Set the default year to {year} in \"{}\"", config::config_path().display()),
+        Err(e) => cli::fail(Exit::Environment, format!("Failed to set the default year: {e}")),
+    }
 }
 
 fn main() {
-    let day = match parse_args() {
-        Ok(day) => day,
-        Err(_) => {
-            eprintln!("Need to specify a day (as integer). example: `cargo scaffold 7`");
-            process::exit(1);
+    let args = match parse_args() {
+        Ok(args) => args,
+        Err(pico_args::Error::MissingArgument | pico_args::Error::Utf8ArgumentParsingFailed { .. }) => {
+            cli::usage_error("scaffold", "need to specify a day (as integer), e.g. `cargo aoc scaffold 7`")
         }
+        Err(e) => cli::usage_error("scaffold", e),
     };
-
-    let day_padded = format!("{day:02}");
-
-    let input_path = format!("src/inputs/{day_padded}.txt");
-    let example_path = format!("src/examples/{day_padded}.txt");
-    let module_path = format!("src/bin/{day_padded}.rs");
-
-    let mut file = match safe_create_file(&module_path) {
-        Ok(file) => file,
-        Err(e) => {
-            eprintln!("Failed to create module file: {e}");
-            process::exit(1);
+    let day = cli::check_day("scaffold", args.day);
+    set_default_year(&args);
+    input::set_year(args.site.year());
+
+    // Days of other years than the default one are named `YYYY-NN`, see `input::bin_name`.
+    let bin_name = input::bin_name(day);
+    let module_path = input::project_dir()
+        .join("src")
+        .join("bin")
+        .join(format!("{bin_name}.rs"));
+    let example_path = input::file_path("examples", day);
+    let is_empty = |path: &Path| fs::metadata(path).map_or(true, |metadata| metadata.len() == 0);
+
+    // An empty example file was most likely left by an earlier scaffold, so it may be filled in.
+    // A dry run only says what would be fetched, it does not make any requests.
+    let fetch = args.fetch && is_empty(&example_path);
+    if fetch && args.dry_run {
+        println!("Would fetch the puzzle page for day {day}, {}", args.site.year());
+    }
+    let page = (fetch && !args.dry_run).then(|| fetch_page(&args));
+    let example = page.as_deref().and_then(fetched_example);
+
+    let files = [
+        ("module", module_path),
+        ("input", input::file_path("inputs", day)),
+        ("example", example_path),
+    ];
+
+    let mut created = 0;
+    for (kind, path) in files {
+        let fill_example = kind == "example" && (example.is_some() || (fetch && args.dry_run));
+        if path.exists() && !(fill_example && is_empty(&path)) {
+            println!("Skipped existing {kind} file \"{}\"", path.display());
+            continue;
         }
-    };
 
-    match file.write_all(MODULE_TEMPLATE.replace("DAY", &day.to_string()).as_bytes()) {
-        Ok(_) => {
-            println!("Created module file \"{}\"", &module_path);
-        }
-        Err(e) => {
-            eprintln!("Failed to write module contents: {e}");
-            process::exit(1);
+        if args.dry_run {
+            let description = match kind {
+                "example" if fetch => format!("{kind} file from the puzzle page, or an empty one if it has no example"),
+                "module" => {
+                    // Rendered all the same, so that a broken template shows up in a dry run too.
+                    render_module(&args, None);
+                    format!("{kind} file")
+                }
+                _ => format!("empty {kind} file"),
+            };
+            println!("Would create {description} \"{}\"", path.display());
+            continue;
         }
-    }
 
-    match create_file(&input_path) {
-        Ok(_) => {
-            println!("Created empty input file \"{}\"", &input_path);
-        }
-        Err(e) => {
-            eprintln!("Failed to create input file: {e}");
-            process::exit(1);
+        let (contents, description) = match (kind, &example) {
+            ("module", _) => (render_module(&args, page.as_deref()), format!("{kind} file")),
+            ("example", Some(example)) => (example.contents().to_string(), format!("{kind} file from the puzzle page")),
+            _ => (String::new(), format!("empty {kind} file")),
+        };
+
+        let written = match path.exists() {
+            true => input::write_file(&path, &contents),
+            false => create_file(&path, &contents),
+        };
+        match written {
+            Ok(_) => {
+                println!("Created {description} \"{}\"", path.display());
+                created += 1;
+            }
+            Err(e) => cli::fail(Exit::Environment, format!("Failed to create {kind} file: {e}")),
         }
     }
 
-    match create_file(&example_path) {
-        Ok(_) => {
-            println!("Created empty example file \"{}\"", &example_path);
-        }
-        Err(e) => {
-            eprintln!("Failed to create example file: {e}");
-            process::exit(1);
+    println!("---");
+    if args.dry_run {
+        println!("Dry run, nothing was written.");
+    } else {
+        if created == 0 {
+            println!("Day {bin_name} is already scaffolded, nothing was created.");
         }
+        println!("🎄 Type `cargo solve {bin_name}` to run your solution.");
     }
-
-    println!("---");
-    println!(
-        "🎄 Type `cargo solve {}` to run your solution.",
-        &day_padded
-    );
 }
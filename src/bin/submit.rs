@@ -0,0 +1,139 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+// Solutions are compiled into this binary, so any features they use need to be enabled here too.
+#![cfg_attr(not(test), feature(int_roundings))]
+
+// The registry compiles every solution in `src/bin` into this binary, see `src/main.rs`.
+#[cfg(not(test))]
+mod days {
+    include!(concat!(env!("OUT_DIR"), "/days.rs"));
+}
+
+#[cfg(not(test))]
+fn main() {
+    use advent_of_code::args::SiteArgs;
+    use advent_of_code::cli::{self, Exit};
+    use advent_of_code::input::InputSource;
+    use advent_of_code::submissions::{self, Entry, Outcome};
+    use advent_of_code::{answers, Status};
+
+    struct Args {
+        day: u8,
+        part: u8,
+        site: SiteArgs,
+    }
+
+    fn parse_args() -> Result<Args, pico_args::Error> {
+        let mut args = pico_args::Arguments::from_env();
+        cli::handle_help(&mut args, "submit");
+        let site = SiteArgs::parse(&mut args)?;
+        let parsed = Args {
+            day: args.free_from_str()?,
+            part: args.free_from_str()?,
+            site,
+        };
+        cli::finish(args)?;
+        Ok(parsed)
+    }
+
+    let args = match parse_args() {
+        Ok(args) if args.part == 1 || args.part == 2 => args,
+        Ok(_) => cli::usage_error(
+            "submit",
+            "the part needs to be 1 or 2, e.g. `cargo aoc submit 1 2`",
+        ),
+        Err(e) => cli::usage_error("submit", e),
+    };
+    let (day, part, year) = (
+        cli::check_day("submit", args.day),
+        args.part,
+        args.site.year(),
+    );
+    advent_of_code::input::set_year(year);
+
+    println!("Solving day {day}, part {part}...");
+    let result = days::registry().solve(day, &InputSource::Puzzle);
+    let solved = &result.parts[usize::from(part) - 1];
+    let answer = match (&solved.status, &solved.answer) {
+        (Status::Solved, Some(answer)) => answer.clone(),
+        (status, _) => {
+            let reason = status
+                .message()
+                .unwrap_or_else(|| status.name().to_string());
+            cli::fail(
+                Exit::Failure,
+                format!("Part {part} of day {day} has no answer to submit: {reason}"),
+            );
+        }
+    };
+    if answer.contains('\n') {
+        cli::fail(
+            Exit::Failure,
+            format!("The answer spans several lines:\n{answer}\nRead the solution off it and submit it on the website."),
+        );
+    }
+
+    let log = submissions::load().unwrap_or_else(|e| {
+        cli::fail(
+            Exit::Environment,
+            format!("Could not read the submission log: {e}"),
+        )
+    });
+    if let Some(reason) = submissions::check(&log, year, day, part, &answer) {
+        cli::fail(Exit::Failure, format!("Not submitting: {reason}."));
+    }
+
+    let client = args
+        .site
+        .client()
+        .unwrap_or_else(|e| cli::fail(Exit::Environment, format!("Failed to submit answer: {e}")));
+
+    println!(
+        "Submitting {answer} for day {day}, part {part} of {year} to {}...",
+        client.base_url()
+    );
+    let outcome = match client.submit(year, day, part, &answer) {
+        Ok(page) => Outcome::parse(&page),
+        Err(e) => cli::fail(Exit::Environment, format!("Failed to submit answer: {e}")),
+    };
+
+    if let Err(e) = submissions::append(&Entry::new(year, day, part, &answer, outcome.clone())) {
+        eprintln!("Could not write the submission log: {e}");
+    }
+
+    match &outcome {
+        Outcome::Correct => {
+            println!("✔ {answer} is the right answer!");
+            match answers::load(day) {
+                Ok(mut accepted) => {
+                    accepted.accept(std::slice::from_ref(solved));
+                    match answers::save(day, &accepted) {
+                        Ok(path) => println!("Recorded accepted answer in \"{}\".", path.display()),
+                        Err(e) => eprintln!("Could not write accepted answers: {e}"),
+                    }
+                }
+                Err(e) => eprintln!("Could not read accepted answers: {e}"),
+            }
+        }
+        Outcome::TooHigh => println!("✘ {answer} is too high."),
+        Outcome::TooLow => println!("✘ {answer} is too low."),
+        Outcome::Wrong => println!("✘ {answer} is not the right answer."),
+        Outcome::RateLimited(Some(wait)) => println!(
+            "Answered too recently, wait {}s before trying again.",
+            wait.as_secs()
+        ),
+        Outcome::RateLimited(None) => {
+            println!("Answered too recently, wait a bit before trying again.")
+        }
+        Outcome::WrongLevel => {
+            println!("Part {part} cannot be answered right now. Was it already solved?")
+        }
+        Outcome::Unknown(text) => println!("Could not make sense of the response: {text}"),
+    }
+
+    if outcome != Outcome::Correct {
+        Exit::Failure.exit();
+    }
+}
@@ -0,0 +1,67 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use advent_of_code::checksum::{self, Check};
+use advent_of_code::cli::{self, Exit};
+use advent_of_code::input;
+
+fn parse_args() -> Result<(Option<u8>, Option<i32>), pico_args::Error> {
+    let mut args = pico_args::Arguments::from_env();
+    cli::handle_help(&mut args, "verify");
+    let year = args.opt_value_from_str(["-y", "--year"])?;
+    let day = args.opt_free_from_str()?;
+    cli::finish(args)?;
+    Ok((day, year))
+}
+
+fn main() {
+    let days = match parse_args() {
+        Ok((day, year)) => {
+            if let Some(year) = year {
+                input::set_year(year);
+            }
+            match day {
+                Some(day) => cli::check_day("verify", day)..=day,
+                None => 1..=25,
+            }
+        }
+        Err(e) => cli::usage_error("verify", e),
+    };
+    let single = days.start() == days.end();
+
+    let mut failed = false;
+    for day in days {
+        let path = input::file_path("inputs", day);
+        match checksum::check(&path) {
+            // Days without an input are only worth mentioning when asked about.
+            Ok(Check::Missing) if !single => {}
+            Ok(check) => {
+                let mark = match check {
+                    Check::Unchanged => "✔",
+                    _ if check.failed() => "✘",
+                    _ => "·",
+                };
+                println!("{mark} Day {day:02}: \"{}\" is {check}", path.display());
+                failed |= check.failed();
+            }
+            Err(e) => {
+                eprintln!(
+                    "✘ Day {day:02}: could not check \"{}\": {e}",
+                    path.display()
+                );
+                failed = true;
+            }
+        }
+    }
+
+    if failed {
+        eprintln!("---");
+        let year: String = input::year_args()
+            .iter()
+            .map(|arg| format!(" {arg}"))
+            .collect();
+        eprintln!("Some inputs differ from what was downloaded. Download them again with `cargo download <day> --force{year}`.");
+        Exit::Failure.exit();
+    }
+}
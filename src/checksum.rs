@@ -0,0 +1,132 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use crate::input;
+use sha2::{Digest, Sha256};
+use std::fmt::{self, Display};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// The state of an input compared to the checksum recorded when it was downloaded.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Check {
+    /// The input is unchanged since it was downloaded.
+    Unchanged,
+    /// The input does not exist.
+    Missing,
+    /// No checksum was recorded, e.g. because the input was not downloaded.
+    Unrecorded,
+    /// The input is empty, but was not when it was downloaded.
+    Empty,
+    /// The input was edited or truncated. Holds the recorded and the current checksum.
+    Changed(String, String),
+}
+
+impl Check {
+    /// Whether the input differs from what was downloaded.
+    pub fn failed(&self) -> bool {
+        matches!(self, Check::Empty | Check::Changed(..))
+    }
+}
+
+impl Display for Check {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Check::Unchanged => write!(f, "unchanged since download"),
+            Check::Missing => write!(f, "missing"),
+            Check::Unrecorded => write!(f, "without a recorded checksum"),
+            Check::Empty => write!(f, "empty, but was not when it was downloaded"),
+            Check::Changed(expected, actual) => write!(
+                f,
+                "edited or truncated since download (expected sha256 {expected}, found {actual})"
+            ),
+        }
+    }
+}
+
+/// The SHA-256 of `contents`, as lowercase hex.
+pub fn digest(contents: &str) -> String {
+    Sha256::digest(contents.as_bytes())
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect()
+}
+
+/// Where the checksum of the file at `path` is recorded, e.g. `src/inputs/01.txt.sha256`.
+pub fn sidecar_path(path: &Path) -> PathBuf {
+    let mut sidecar = path.as_os_str().to_owned();
+    sidecar.push(".sha256");
+    PathBuf::from(sidecar)
+}
+
+/// Records the checksum of `contents` as that of the file at `path`. The sidecar has the format
+/// of `sha256sum`, so it can also be checked with `sha256sum -c`.
+pub fn record(path: &Path, contents: &str) -> io::Result<()> {
+    let name = path
+        .file_name()
+        .unwrap_or_default()
+        .to_string_lossy();
+    input::write_file(
+        &sidecar_path(path),
+        &format!("{}  {name}\n", digest(contents)),
+    )
+}
+
+/// Compares the file at `path` to its recorded checksum.
+pub fn check(path: &Path) -> io::Result<Check> {
+    let contents = match fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Check::Missing),
+        Err(e) => return Err(e),
+    };
+    let expected = match fs::read_to_string(sidecar_path(path)) {
+        Ok(sidecar) => sidecar
+            .split_whitespace()
+            .next()
+            .unwrap_or_default()
+            .to_lowercase(),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Check::Unrecorded),
+        Err(e) => return Err(e),
+    };
+
+    let actual = digest(&contents);
+    Ok(match actual == expected {
+        true => Check::Unchanged,
+        false if contents.is_empty() => Check::Empty,
+        false => Check::Changed(expected, actual),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+    use std::process;
+
+    #[test]
+    fn test_check() {
+        assert_eq!(
+            digest("abc"),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+
+        let dir = env::temp_dir().join(format!("aoc-checksum-{}", process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("01.txt");
+        assert_eq!(check(&path).unwrap(), Check::Missing);
+
+        fs::write(&path, "1\n2\n").unwrap();
+        assert_eq!(check(&path).unwrap(), Check::Unrecorded);
+        record(&path, "1\n2\n").unwrap();
+        assert_eq!(check(&path).unwrap(), Check::Unchanged);
+
+        fs::write(&path, "1\n").unwrap();
+        assert!(matches!(check(&path).unwrap(), Check::Changed(..)));
+        fs::write(&path, "").unwrap();
+        assert_eq!(check(&path).unwrap(), Check::Empty);
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
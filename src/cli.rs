@@ -0,0 +1,360 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use std::fmt::Display;
+use std::process;
+
+/// The exit codes of all commands.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Exit {
+    Success = 0,
+    /// The command ran, but what it checked did not hold up: a part failed or gave a wrong
+    /// answer, an input changed since download, or a benchmark regressed.
+    Failure = 1,
+    /// The arguments could not be parsed.
+    Usage = 2,
+    /// The command could not do its work, e.g. because there is no session cookie, the website
+    /// could not be reached, or a file could not be read or written.
+    Environment = 3,
+}
+
+impl Exit {
+    pub fn exit(self) -> ! {
+        process::exit(self as i32)
+    }
+}
+
+/// Prints `message` to stderr and exits with `code`.
+pub fn fail(code: Exit, message: impl Display) -> ! {
+    eprintln!("{message}");
+    code.exit()
+}
+
+/// A subcommand of `cargo aoc`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Command {
+    pub name: &'static str,
+    pub about: &'static str,
+    /// The arguments after the name of the command.
+    pub usage: &'static str,
+    /// Each option with a description.
+    pub options: &'static [(&'static str, &'static str)],
+}
+
+const YEAR: (&str, &str) = (
+    "-y, --year <year>",
+    "The event to use instead of the default year in aoc.toml",
+);
+const BASE_URL: (&str, &str) = (
+    "--base-url <url>",
+    "The website to talk to instead of the one in aoc.toml",
+);
+const RELEASE: (&str, &str) = (
+    "--release",
+    "Build with optimizations, see `cargo build --release`",
+);
+const FEATURES: (&str, &str) = (
+    "--features <features>",
+    "Build with these cargo features, e.g. `memory`",
+);
+const FORMAT: (&str, &str) = (
+    "--format <text|json|csv>",
+    "How to print the results (default: text)",
+);
+const EXAMPLE: (&str, &str) = (
+    "--example",
+    "Use the example input instead of the puzzle input",
+);
+const INPUT: (&str, &str) = (
+    "--input <path>, -",
+    "Read the input from a file, or from stdin with `-`",
+);
+const LOG: (&str, &str) = (
+    "--log <filter>",
+    "Print the traces of the solution, e.g. `debug` or `warn,9=trace`",
+);
+const LOG_FILE: (&str, &str) = (
+    "--log-file <path>",
+    "Write the traces to a file instead of stderr",
+);
+
+/// All subcommands, in the order `cargo aoc --help` lists them.
+pub const COMMANDS: &[Command] = &[
+    Command {
+        name: "scaffold",
+        about: "Creates the solution, input and example files of a day",
+        usage: "<day> [options]",
+        options: &[
+            (
+                "--template <name>",
+                "The template in templates/ to render (default: default)",
+            ),
+            ("--title <title>", "The puzzle title to put in the module"),
+            (
+                "--fetch",
+                "Fill in the example and expected answers from the puzzle page",
+            ),
+            (
+                "--dry-run",
+                "Print what would be created without writing anything",
+            ),
+            YEAR,
+            BASE_URL,
+        ],
+    },
+    Command {
+        name: "download",
+        about: "Downloads the puzzle input of a day",
+        usage: "<day> [options]",
+        options: &[
+            (
+                "--puzzle",
+                "Also download the puzzle description as Markdown",
+            ),
+            ("--force", "Overwrite an input that was already downloaded"),
+            YEAR,
+            BASE_URL,
+        ],
+    },
+    Command {
+        name: "solve",
+        about: "Solves a day",
+        usage: "<day> [options]",
+        options: &[
+            EXAMPLE,
+            INPUT,
+            ("--accept", "Record the answers as the accepted ones"),
+            (
+                "--bench",
+                "Benchmark the day instead, see `cargo aoc bench --help`",
+            ),
+            FORMAT,
+            LOG,
+            LOG_FILE,
+            RELEASE,
+            FEATURES,
+            YEAR,
+        ],
+    },
+    Command {
+        name: "all",
+        about: "Solves all days of a year",
+        usage: "[options]",
+        options: &[
+            EXAMPLE,
+            (
+                "--day-timeout <seconds>",
+                "Stop a day that runs longer than this (default: 60)",
+            ),
+            (
+                "--timeout <seconds>",
+                "Stop parsing or a part that runs longer than this",
+            ),
+            (
+                "--memory-limit <MiB>",
+                "Stop a day that needs more heap memory than this",
+            ),
+            FORMAT,
+            LOG,
+            LOG_FILE,
+            (
+                "--debug",
+                "Build without optimizations, e.g. to write trace statements",
+            ),
+            FEATURES,
+            YEAR,
+        ],
+    },
+    Command {
+        name: "bench",
+        about: "Benchmarks a day in an optimized build",
+        usage: "<day> [options]",
+        options: &[
+            (
+                "--warmup <n>",
+                "Unmeasured runs before measuring (default: 3)",
+            ),
+            ("--iterations <n>", "Measure exactly this many runs"),
+            ("--time <seconds>", "Measure for this long (default: 3)"),
+            (
+                "--compare",
+                "Compare against an earlier run and fail if it got slower",
+            ),
+            (
+                "--baseline <commit>",
+                "The commit to compare against (default: the latest run)",
+            ),
+            (
+                "--threshold <percent>",
+                "How much slower counts as a regression (default: 5)",
+            ),
+            EXAMPLE,
+            INPUT,
+            FORMAT,
+            FEATURES,
+            YEAR,
+        ],
+    },
+    Command {
+        name: "submit",
+        about: "Solves a part of a day and submits the answer",
+        usage: "<day> <part> [options]",
+        options: &[RELEASE, FEATURES, YEAR, BASE_URL],
+    },
+    Command {
+        name: "test",
+        about: "Runs the tests of all days, or of a single day",
+        usage: "[<day>] [options] [-- <test options>]",
+        options: &[
+            YEAR,
+            ("<options>", "Any other option is passed on to `cargo test`"),
+        ],
+    },
+    Command {
+        name: "verify",
+        about: "Checks that the inputs are unchanged since download",
+        usage: "[<day>] [options]",
+        options: &[YEAR],
+    },
+    Command {
+        name: "doctor",
+        about: "Checks the setup and tells how to fix what is missing",
+        usage: "[options]",
+        options: &[
+            (
+                "--offline",
+                "Do not check the session cookie with the website",
+            ),
+            YEAR,
+            BASE_URL,
+        ],
+    },
+];
+
+/// The subcommand called `name`.
+pub fn command(name: &str) -> Option<&'static Command> {
+    COMMANDS
+        .iter()
+        .find(|command| command.name == name)
+}
+
+impl Command {
+    pub fn usage_line(&self) -> String {
+        format!("cargo aoc {} {}", self.name, self.usage)
+    }
+
+    pub fn help(&self) -> String {
+        let mut options = self.options.to_vec();
+        options.push(("-h, --help", "Print this help"));
+        format!(
+            "{}\n\nUsage: {}\n\nOptions:\n{}",
+            self.about,
+            self.usage_line(),
+            columns(&options)
+        )
+    }
+}
+
+/// The help of `cargo aoc` itself.
+pub fn help() -> String {
+    let commands: Vec<(&str, &str)> = COMMANDS
+        .iter()
+        .map(|command| (command.name, command.about))
+        .collect();
+    format!(
+        "Solves Advent of Code puzzles in this repository.\n\nUsage: cargo aoc <command> [options]\n\nCommands:\n{}\n\nRun `cargo aoc <command> --help` for the options of a command.",
+        columns(&commands)
+    )
+}
+
+fn columns(rows: &[(&str, &str)]) -> String {
+    let width = rows
+        .iter()
+        .map(|(left, _)| left.len())
+        .max()
+        .unwrap_or(0);
+    rows.iter()
+        .map(|(left, right)| format!("  {left:width$}  {right}"))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Prints the help of the command `name` and exits if `-h` or `--help` was passed.
+pub fn handle_help(args: &mut pico_args::Arguments, name: &str) {
+    if args.contains(["-h", "--help"]) {
+        let command = command(name).unwrap_or_else(|| panic!("there is no command \"{name}\""));
+        println!("{}", command.help());
+        Exit::Success.exit();
+    }
+}
+
+/// Fails if `args` has arguments left that no option took.
+pub fn finish(args: pico_args::Arguments) -> Result<(), pico_args::Error> {
+    let remaining = args.finish();
+    match remaining.is_empty() {
+        true => Ok(()),
+        false => Err(pico_args::Error::ArgumentParsingFailed {
+            cause: format!("unexpected arguments: {remaining:?}"),
+        }),
+    }
+}
+
+/// Reports arguments to the command `name` that could not be parsed and exits.
+pub fn usage_error(name: &str, error: impl Display) -> ! {
+    let usage = command(name).map_or_else(
+        || "cargo aoc <command> [options]".to_string(),
+        Command::usage_line,
+    );
+    fail(
+        Exit::Usage,
+        format!("error: {error}\n\nUsage: {usage}\n\nRun `cargo aoc {name} --help` for more information."),
+    )
+}
+
+/// Returns `day` if the event has such a day, and reports it as a usage error of `name` otherwise.
+pub fn check_day(name: &str, day: u8) -> u8 {
+    match day {
+        1..=25 => day,
+        _ => usage_error(
+            name,
+            format!("invalid day \"{day}\", expected a number from 1 to 25"),
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_help() {
+        let submit = command("submit")
+            .unwrap()
+            .help();
+        assert!(submit.starts_with("Solves a part of a day and submits the answer\n\nUsage: cargo aoc submit <day> <part> [options]\n"));
+        assert!(submit.contains("\n  -y, --year <year>      The event"));
+        assert!(submit.ends_with("\n  -h, --help             Print this help"));
+
+        assert!(command("aoc").is_none());
+        let solve = command("solve")
+            .unwrap()
+            .help();
+        let filters: Vec<&str> = LOG
+            .1
+            .split('`')
+            .skip(1)
+            .step_by(2)
+            .collect();
+        assert_eq!(filters, ["debug", "warn,9=trace"]);
+        for filter in filters {
+            assert!(solve.contains(&format!("`{filter}`")));
+            assert!(filter
+                .parse::<crate::trace::Filter>()
+                .is_ok());
+        }
+        for command in COMMANDS {
+            assert!(help().contains(&format!("\n  {:8}  {}", command.name, command.about)));
+        }
+    }
+}
@@ -0,0 +1,485 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use crate::config;
+use std::env;
+use std::fmt::{self, Display};
+use std::fs;
+use std::path::PathBuf;
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// The session cookie of your Advent of Code account.
+pub const SESSION_VAR: &str = "AOC_SESSION";
+/// Points the client at another server, e.g. a local stand-in for testing.
+pub const BASE_URL_VAR: &str = "AOC_BASE_URL";
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+/// Identifies this tool to the Advent of Code servers, as their maintainer asks automated tools to,
+/// along with the `contact` in `aoc.toml` so that they can reach whoever runs it.
+pub fn user_agent(contact: Option<&str>) -> String {
+    let name = concat!("advent_of_code/", env!("CARGO_PKG_VERSION"));
+    match contact {
+        Some(contact) => format!("{name} (+{contact})"),
+        None => name.to_string(),
+    }
+}
+
+/// Why a request to Advent of Code failed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ClientError {
+    /// No session cookie was found.
+    NoSession,
+    /// The server did not accept the session cookie, which most likely expired.
+    SessionExpired,
+    /// The puzzle is not unlocked yet. Holds the time left until it is, if known.
+    Locked(Option<Duration>),
+    /// The server answered with an error.
+    Http(u16, String),
+    /// No answer was received.
+    Transport(String),
+}
+
+impl Display for ClientError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ClientError::NoSession => write!(
+                f,
+                "no session cookie found. Set {SESSION_VAR} or save the cookie to \"{}\".",
+                session_paths()
+                    .first()
+                    .map_or_else(|| "~/.adventofcode.session".to_string(), |path| path.display().to_string())
+            ),
+            ClientError::SessionExpired => write!(
+                f,
+                "the session cookie was rejected. It has probably expired, log in again and copy the new one."
+            ),
+            ClientError::Locked(Some(left)) => {
+                let seconds = left.as_secs();
+                write!(
+                    f,
+                    "the puzzle is not unlocked yet. It unlocks in {}h {:02}m {:02}s.",
+                    seconds / 3600,
+                    seconds % 3600 / 60,
+                    seconds % 60
+                )
+            }
+            ClientError::Locked(None) => write!(f, "the puzzle is not unlocked yet."),
+            ClientError::Http(status, body) => {
+                // Error pages can be whole HTML documents, their first line is enough to go on.
+                let summary: String = body
+                    .lines()
+                    .map(str::trim)
+                    .find(|line| !line.is_empty())
+                    .unwrap_or_default()
+                    .chars()
+                    .take(200)
+                    .collect();
+                write!(f, "the server responded with {status}: {summary}")
+            }
+            ClientError::Transport(e) => write!(f, "could not reach the server: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for ClientError {}
+
+/// Days since 1970-01-01 of a date in the proleptic Gregorian calendar.
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+/// When a puzzle unlocks: midnight in UTC-5 on the day in December.
+pub fn unlock_time(year: i32, day: u8) -> SystemTime {
+    let days = days_from_civil(year.into(), 12, day.into());
+    UNIX_EPOCH + Duration::from_secs((days * 86_400 + 5 * 3_600) as u64)
+}
+
+/// How long until a puzzle unlocks, or `None` if it already has.
+pub fn unlocks_in(year: i32, day: u8) -> Option<Duration> {
+    unlock_time(year, day)
+        .duration_since(SystemTime::now())
+        .ok()
+}
+
+/// The year of the most recent event that has started.
+pub fn latest_year() -> i32 {
+    let now = SystemTime::now();
+    let mut year = 2015;
+    while unlock_time(year + 1, 1) <= now {
+        year += 1;
+    }
+    year
+}
+
+/// Where the session cookie is looked for if [`SESSION_VAR`] is not set: the `session_file` in
+/// `aoc.toml`, or else the files `aoc-cli` uses, so an existing setup keeps working.
+pub fn session_paths() -> Vec<PathBuf> {
+    if let Some(path) = &config::get()
+        .paths
+        .session_file
+    {
+        return vec![config::resolve(path)];
+    }
+    let home = env::var_os("HOME").map(PathBuf::from);
+    let config = env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| {
+            home.as_ref()
+                .map(|home| home.join(".config"))
+        });
+    [
+        home.map(|home| home.join(".adventofcode.session")),
+        config.map(|config| config.join("adventofcode.session")),
+    ]
+    .into_iter()
+    .flatten()
+    .collect()
+}
+
+/// Reads the session cookie from [`SESSION_VAR`] or from the [`session_paths`].
+pub fn find_session() -> Result<String, ClientError> {
+    if let Ok(session) = env::var(SESSION_VAR) {
+        if !session.trim().is_empty() {
+            return Ok(session.trim().to_string());
+        }
+    }
+    session_paths()
+        .into_iter()
+        .find_map(|path| fs::read_to_string(path).ok())
+        .map(|session| session.trim().to_string())
+        .filter(|session| !session.is_empty())
+        .ok_or(ClientError::NoSession)
+}
+
+/// Whether a failed request may succeed if it is sent again.
+fn is_transient(error: &ClientError) -> bool {
+    match error {
+        ClientError::Transport(_) => true,
+        ClientError::Http(status, _) => *status == 429 || *status >= 500,
+        _ => false,
+    }
+}
+
+/// Turns an error response into a [`ClientError`].
+fn classify(status: u16, body: String) -> ClientError {
+    let lowercase = body.to_lowercase();
+    match status {
+        400 | 401 | 403 => ClientError::SessionExpired,
+        // A redirect to the login page that was not followed.
+        300..=399 if lowercase.contains("log in") || lowercase.contains("login") => {
+            ClientError::SessionExpired
+        }
+        404 if lowercase.contains("unlock") => ClientError::Locked(None),
+        _ => ClientError::Http(status, body),
+    }
+}
+
+/// The account named in the header of a page, e.g. `<div class="user">Name <span ...`.
+fn logged_in_user(page: &str) -> Option<String> {
+    let (_, rest) = page.split_once("<div class=\"user\">")?;
+    let name = rest.split('<').next()?.trim();
+    (!name.is_empty()).then(|| name.to_string())
+}
+
+/// A client for the Advent of Code website.
+pub struct Client {
+    base_url: String,
+    session: String,
+    agent: ureq::Agent,
+    retries: u32,
+    backoff: Duration,
+}
+
+impl Client {
+    pub fn new(base_url: &str, session: &str) -> Self {
+        Self {
+            base_url: base_url
+                .trim_end_matches('/')
+                .to_string(),
+            session: session.to_string(),
+            agent: ureq::AgentBuilder::new()
+                .user_agent(&user_agent(
+                    config::get()
+                        .site
+                        .contact
+                        .as_deref(),
+                ))
+                .timeout(Duration::from_secs(30))
+                .build(),
+            retries: 3,
+            backoff: Duration::from_secs(1),
+        }
+    }
+
+    /// A client for the server in [`BASE_URL_VAR`], the `base_url` in `aoc.toml`, or else
+    /// adventofcode.com, with the session cookie from [`find_session`].
+    pub fn from_env() -> Result<Self, ClientError> {
+        let base_url = env::var(BASE_URL_VAR)
+            .ok()
+            .filter(|url| !url.is_empty())
+            .or_else(|| {
+                config::get()
+                    .site
+                    .base_url
+                    .clone()
+            })
+            .unwrap_or_else(|| DEFAULT_BASE_URL.to_string());
+        Ok(Self::new(&base_url, &find_session()?))
+    }
+
+    /// Sets how often a request is retried after a transient error, waiting `backoff` before the
+    /// first retry and twice as long before each one after that.
+    pub fn with_retries(mut self, retries: u32, backoff: Duration) -> Self {
+        self.retries = retries;
+        self.backoff = backoff;
+        self
+    }
+
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    /// Sends a GET request for `path`, or a POST request if there is a `form`.
+    fn request(&self, path: &str, form: Option<&[(&str, &str)]>) -> Result<String, ClientError> {
+        let url = format!("{}{path}", self.base_url);
+        let cookie = format!("session={}", self.session);
+        let response = match form {
+            Some(form) => self
+                .agent
+                .post(&url)
+                .set("Cookie", &cookie)
+                .send_form(form),
+            None => self
+                .agent
+                .get(&url)
+                .set("Cookie", &cookie)
+                .call(),
+        };
+        match response {
+            Ok(response) => response
+                .into_string()
+                .map_err(|e| ClientError::Transport(e.to_string())),
+            Err(ureq::Error::Status(status, response)) => Err(classify(
+                status,
+                response
+                    .into_string()
+                    .unwrap_or_default(),
+            )),
+            Err(ureq::Error::Transport(e)) => Err(ClientError::Transport(e.to_string())),
+        }
+    }
+
+    /// Sends a GET request for `path`, retrying transient errors with exponential backoff.
+    pub fn get(&self, path: &str) -> Result<String, ClientError> {
+        let mut backoff = self.backoff;
+        let mut attempt = 0;
+        loop {
+            match self.request(path, None) {
+                Err(e) if is_transient(&e) && attempt < self.retries => {
+                    attempt += 1;
+                    eprintln!(
+                        "Request failed, retrying in {backoff:.0?} ({attempt}/{}): {e}",
+                        self.retries
+                    );
+                    thread::sleep(backoff);
+                    backoff *= 2;
+                }
+                result => return result,
+            }
+        }
+    }
+
+    /// Downloads the puzzle input of `day` in `year`.
+    pub fn input(&self, year: i32, day: u8) -> Result<String, ClientError> {
+        if let Some(left) = unlocks_in(year, day) {
+            return Err(ClientError::Locked(Some(left)));
+        }
+        self.get(&format!("/{year}/day/{day}/input"))
+    }
+
+    /// Downloads the puzzle page of `day` in `year`. It describes part two once part one is solved.
+    pub fn puzzle(&self, year: i32, day: u8) -> Result<String, ClientError> {
+        if let Some(left) = unlocks_in(year, day) {
+            return Err(ClientError::Locked(Some(left)));
+        }
+        self.get(&format!("/{year}/day/{day}"))
+    }
+
+    /// The name of the account that the session cookie belongs to, read from the header of the
+    /// small about page of `year`. Fails with [`ClientError::SessionExpired`] if the page shows no
+    /// one logged in.
+    pub fn user(&self, year: i32) -> Result<String, ClientError> {
+        if let Some(left) = unlocks_in(year, 1) {
+            return Err(ClientError::Locked(Some(left)));
+        }
+        let page = self.get(&format!("/{year}/about"))?;
+        logged_in_user(&page).ok_or(ClientError::SessionExpired)
+    }
+
+    /// Submits `answer` for `part` of `day` in `year` and returns the response page. Submissions
+    /// are never retried, as a repeated request could count as another attempt.
+    pub fn submit(
+        &self,
+        year: i32,
+        day: u8,
+        part: u8,
+        answer: &str,
+    ) -> Result<String, ClientError> {
+        if let Some(left) = unlocks_in(year, day) {
+            return Err(ClientError::Locked(Some(left)));
+        }
+        let level = part.to_string();
+        self.request(
+            &format!("/{year}/day/{day}/answer"),
+            Some(&[("level", level.as_str()), ("answer", answer)]),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+
+    /// Serves one canned response per connection and returns the requests it received.
+    fn stand_in(responses: Vec<(u16, &'static str)>) -> (String, thread::JoinHandle<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let handle = thread::spawn(move || {
+            let mut requests = vec![];
+            for (status, body) in responses {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut request = String::new();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                loop {
+                    let mut line = String::new();
+                    reader
+                        .read_line(&mut line)
+                        .unwrap();
+                    if line.trim().is_empty() {
+                        break;
+                    }
+                    request.push_str(&line);
+                }
+                requests.push(request);
+                write!(
+                    stream,
+                    "HTTP/1.1 {status} Status\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                    body.len()
+                )
+                .unwrap();
+            }
+            requests
+        });
+        (url, handle)
+    }
+
+    #[test]
+    fn test_unlock_time() {
+        assert_eq!(days_from_civil(1970, 1, 1), 0);
+        assert_eq!(
+            unlock_time(2022, 1)
+                .duration_since(UNIX_EPOCH)
+                .unwrap()
+                .as_secs(),
+            1_669_870_800
+        );
+        assert_eq!(unlocks_in(2022, 25), None);
+        assert!(latest_year() >= 2022);
+    }
+
+    #[test]
+    fn test_classify() {
+        let login =
+            "Puzzle inputs differ by user.  Please log in to get your puzzle input.".to_string();
+        assert_eq!(classify(400, login.clone()), ClientError::SessionExpired);
+        assert_eq!(
+            classify(302, "Redirecting to /auth/login".to_string()),
+            ClientError::SessionExpired
+        );
+        assert_eq!(classify(500, login.clone()), ClientError::Http(500, login));
+        assert_eq!(
+            classify(
+                404,
+                "Please don't repeatedly request this endpoint before it unlocks!".to_string()
+            ),
+            ClientError::Locked(None)
+        );
+        assert_eq!(
+            classify(404, "Not Found".to_string()),
+            ClientError::Http(404, "Not Found".to_string())
+        );
+    }
+
+    #[test]
+    fn test_download() {
+        let (url, server) = stand_in(vec![(503, "busy"), (200, "1000\n2000\n")]);
+        let client = Client::new(&url, "secret").with_retries(2, Duration::from_millis(1));
+        assert_eq!(client.input(2022, 1), Ok("1000\n2000\n".to_string()));
+
+        let requests = server.join().unwrap();
+        assert_eq!(requests.len(), 2);
+        assert!(requests[1].starts_with("GET /2022/day/1/input HTTP/1.1"));
+        assert!(requests[1].contains("session=secret"));
+        assert!(requests[1].contains(&user_agent(None)));
+        assert_eq!(
+            user_agent(Some("me@example.com")),
+            format!(
+                "advent_of_code/{} (+me@example.com)",
+                env!("CARGO_PKG_VERSION")
+            )
+        );
+
+        // A server error is retried, even if its page mentions logging in.
+        let (url, server) = stand_in(vec![
+            (500, "Internal error, please log in again later"),
+            (200, "1000\n"),
+        ]);
+        let client = Client::new(&url, "secret").with_retries(2, Duration::from_millis(1));
+        assert_eq!(client.input(2022, 1), Ok("1000\n".to_string()));
+        assert_eq!(server.join().unwrap().len(), 2);
+
+        let (url, server) = stand_in(vec![(400, "Please log in to get your puzzle input.")]);
+        let client = Client::new(&url, "expired").with_retries(2, Duration::from_millis(1));
+        assert_eq!(client.input(2022, 1), Err(ClientError::SessionExpired));
+        assert_eq!(server.join().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_user() {
+        let (url, server) = stand_in(vec![
+            (
+                200,
+                "<header><div class=\"user\">Jane Doe <span class=\"star-count\">50*</span></div></header>",
+            ),
+            (200, "<header><a href=\"/2022/auth/login\">[Log In]</a></header>"),
+        ]);
+        let client = Client::new(&url, "secret");
+        assert_eq!(client.user(2022), Ok("Jane Doe".to_string()));
+        assert_eq!(client.user(2022), Err(ClientError::SessionExpired));
+        assert!(server.join().unwrap()[0].starts_with("GET /2022/about HTTP/1.1"));
+    }
+
+    #[test]
+    fn test_puzzle() {
+        let (url, server) = stand_in(vec![(200, include_str!("fixtures/puzzles/part-one.html"))]);
+        let page = Client::new(&url, "secret")
+            .puzzle(2022, 1)
+            .unwrap();
+        assert!(server.join().unwrap()[0].starts_with("GET /2022/day/1 HTTP/1.1"));
+
+        let example = crate::puzzle::example(&page).unwrap();
+        assert_eq!(
+            example.contents(),
+            "---\npart_one = \"4000\"\n---\n1000\n2000\n\n4000\n"
+        );
+    }
+}
@@ -0,0 +1,352 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use crate::cli::{self, Exit};
+use crate::output::Format;
+use crate::{input, layout};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use std::time::Duration;
+use toml::value::{Table, Value};
+
+const CONFIG_FILE: &str = "aoc.toml";
+
+/// The settings in `aoc.toml` at the root of the repository. Settings that are not given keep
+/// their defaults, and options on the command line take precedence over all of them.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Config {
+    /// The default year, see [`input::default_year`].
+    pub year: Option<i32>,
+    pub paths: Paths,
+    pub site: Site,
+    pub run: Run,
+}
+
+/// The `[paths]` table. Relative paths are resolved by [`resolve`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Paths {
+    /// Where puzzle inputs are kept instead of `src/inputs`.
+    pub inputs: Option<PathBuf>,
+    /// Where examples are kept instead of `src/examples`.
+    pub examples: Option<PathBuf>,
+    /// Where `scaffold` looks for templates instead of `templates`.
+    pub templates: Option<PathBuf>,
+    /// The file holding the session cookie, instead of the files `aoc-cli` uses.
+    pub session_file: Option<PathBuf>,
+}
+
+/// The `[site]` table.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Site {
+    /// The website to talk to instead of adventofcode.com.
+    pub base_url: Option<String>,
+    /// How to reach whoever runs this tool, e.g. an email address or the URL of the repository.
+    /// It is sent along with every request, see [`client::user_agent`](crate::client::user_agent).
+    pub contact: Option<String>,
+}
+
+/// The `[run]` table, defaults for the options of `solve` and `all`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Run {
+    /// The default of `--format`.
+    pub format: Option<Format>,
+    /// The default of `--timeout`.
+    pub timeout: Option<Duration>,
+    /// The default of `--day-timeout`.
+    pub day_timeout: Option<Duration>,
+    /// The default of `--memory-limit`, in bytes.
+    pub memory_limit: Option<usize>,
+}
+
+/// Fails on the first key of `table` that is not in `known`. `prefix` names the table in errors.
+fn check_keys(table: &Table, prefix: &str, known: &[&str]) -> Result<(), String> {
+    match table
+        .keys()
+        .find(|key| !known.contains(&key.as_str()))
+    {
+        Some(key) => Err(format!("unknown setting `{prefix}{key}`")),
+        None => Ok(()),
+    }
+}
+
+/// Reads `key` from `table` with `convert`, which returns `None` for a value that is not what
+/// `expected` describes.
+fn value<T>(
+    table: &Table,
+    prefix: &str,
+    key: &str,
+    expected: &str,
+    convert: impl FnOnce(&Value) -> Option<T>,
+) -> Result<Option<T>, String> {
+    match table.get(key) {
+        None => Ok(None),
+        Some(value) => convert(value)
+            .map(Some)
+            .ok_or_else(|| format!("`{prefix}{key}` needs to be {expected}, got {value}")),
+    }
+}
+
+fn subtable<'a>(table: &'a Table, key: &str) -> Result<Option<&'a Table>, String> {
+    match table.get(key) {
+        None => Ok(None),
+        Some(Value::Table(subtable)) => Ok(Some(subtable)),
+        Some(other) => Err(format!(
+            "`{key}` needs to be a table, got {}",
+            other.type_str()
+        )),
+    }
+}
+
+fn string(value: &Value) -> Option<String> {
+    value
+        .as_str()
+        .map(str::to_string)
+}
+
+fn path(value: &Value) -> Option<PathBuf> {
+    value
+        .as_str()
+        .filter(|path| !path.is_empty())
+        .map(PathBuf::from)
+}
+
+fn seconds(value: &Value) -> Option<Duration> {
+    let seconds = match value {
+        Value::Integer(seconds) => *seconds as f64,
+        Value::Float(seconds) => *seconds,
+        _ => return None,
+    };
+    (seconds.is_finite() && seconds > 0.0).then(|| Duration::from_secs_f64(seconds))
+}
+
+impl Paths {
+    fn parse(table: &Table) -> Result<Self, String> {
+        let prefix = "paths.";
+        check_keys(
+            table,
+            prefix,
+            &[
+                "inputs",
+                "examples",
+                "solutions",
+                "templates",
+                "session_file",
+            ],
+        )?;
+        // Cargo only finds binaries in `src/bin`, so the solutions cannot live anywhere else.
+        value(
+            table,
+            prefix,
+            "solutions",
+            "\"src/bin\", where cargo looks for binaries",
+            |value| {
+                (value
+                    .as_str()?
+                    .trim_end_matches('/')
+                    == "src/bin")
+                    .then_some(())
+            },
+        )?;
+        Ok(Self {
+            inputs: value(table, prefix, "inputs", "a path", path)?,
+            examples: value(table, prefix, "examples", "a path", path)?,
+            templates: value(table, prefix, "templates", "a path", path)?,
+            session_file: value(table, prefix, "session_file", "a path", path)?,
+        })
+    }
+}
+
+impl Site {
+    fn parse(table: &Table) -> Result<Self, String> {
+        check_keys(table, "site.", &["base_url", "contact"])?;
+        Ok(Self {
+            base_url: value(table, "site.", "base_url", "a URL", string)?,
+            contact: value(table, "site.", "contact", "a string", string)?
+                .filter(|contact| !contact.is_empty()),
+        })
+    }
+}
+
+impl Run {
+    fn parse(table: &Table) -> Result<Self, String> {
+        let prefix = "run.";
+        check_keys(
+            table,
+            prefix,
+            &["format", "timeout", "day_timeout", "memory_limit"],
+        )?;
+        Ok(Self {
+            format: value(
+                table,
+                prefix,
+                "format",
+                "\"text\", \"json\" or \"csv\"",
+                |value| value.as_str()?.parse().ok(),
+            )?,
+            timeout: value(
+                table,
+                prefix,
+                "timeout",
+                "a positive number of seconds",
+                seconds,
+            )?,
+            day_timeout: value(
+                table,
+                prefix,
+                "day_timeout",
+                "a positive number of seconds",
+                seconds,
+            )?,
+            memory_limit: value(
+                table,
+                prefix,
+                "memory_limit",
+                "a positive number of MiB",
+                |value| {
+                    let mebibytes = usize::try_from(value.as_integer()?).ok()?;
+                    (mebibytes > 0).then(|| mebibytes.saturating_mul(1024 * 1024))
+                },
+            )?,
+        })
+    }
+}
+
+impl Config {
+    pub fn parse(contents: &str) -> Result<Self, String> {
+        let table: Table = toml::from_str(contents).map_err(|e| e.to_string())?;
+        check_keys(&table, "", &["year", "paths", "site", "run"])?;
+
+        let year = value(&table, "", "year", "a year", |value| {
+            i32::try_from(value.as_integer()?).ok()
+        })?;
+        let empty = Table::new();
+        let section = |key: &str| subtable(&table, key).map(|section| section.unwrap_or(&empty));
+        Ok(Self {
+            year,
+            paths: Paths::parse(section("paths")?)?,
+            site: Site::parse(section("site")?)?,
+            run: Run::parse(section("run")?)?,
+        })
+    }
+}
+
+pub fn config_path() -> PathBuf {
+    input::project_dir().join(CONFIG_FILE)
+}
+
+/// Resolves a path from `aoc.toml`: `~/` is the home directory, and relative paths are relative
+/// to the root of the repository.
+pub fn resolve(path: &Path) -> PathBuf {
+    layout::resolve(&input::project_dir(), path)
+}
+
+/// Reads `aoc.toml`. A missing file means the defaults.
+pub fn read() -> Result<Config, String> {
+    let path = config_path();
+    match fs::read_to_string(&path) {
+        Ok(contents) => Config::parse(&contents).map_err(|e| format!("{}: {e}", path.display())),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Config::default()),
+        Err(e) => Err(format!("{}: {e}", path.display())),
+    }
+}
+
+/// `contents` of `aoc.toml` with `year` set. Top-level keys have to come before the first table,
+/// so it goes first.
+fn with_year(contents: &str, year: i32) -> String {
+    format!("year = {year}\n\n{contents}")
+}
+
+/// Adds `year` to `aoc.toml`, creating the file if there is none. Must be called before the
+/// configuration is read with [`get`], as it is only read once.
+pub fn write_year(year: i32) -> io::Result<()> {
+    let path = config_path();
+    let contents = match fs::read_to_string(&path) {
+        Ok(contents) => contents,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => String::new(),
+        Err(e) => return Err(e),
+    };
+    input::write_file(&path, &with_year(&contents, year))
+}
+
+static CONFIG: OnceLock<Config> = OnceLock::new();
+
+/// Uses `config` instead of what `aoc.toml` holds, e.g. for a dry run of a change to it. Has no
+/// effect once the configuration was read.
+pub fn init(config: Config) {
+    let _ = CONFIG.set(config);
+}
+
+/// The configuration, read once. Ends the process with [`Exit::Environment`] if the file is
+/// invalid, as running with settings other than the ones written down would only confuse.
+pub fn get() -> &'static Config {
+    CONFIG.get_or_init(|| {
+        read()
+            .unwrap_or_else(|e| cli::fail(Exit::Environment, format!("Invalid configuration: {e}")))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(
+            Config::parse("year = 2021\n"),
+            Ok(Config {
+                year: Some(2021),
+                ..Config::default()
+            })
+        );
+        assert_eq!(Config::parse(""), Ok(Config::default()));
+        assert!(Config::parse("year = \"2021\"").is_err());
+        assert!(Config::parse("yaer = 2021").is_err());
+        assert_eq!(read().map(|config| config.year.is_some()), Ok(true));
+        assert_eq!(
+            Config::parse(&with_year("[run]\nformat = \"json\"\n", 2021)).map(|config| config.year),
+            Ok(Some(2021))
+        );
+        assert_eq!(
+            Config::parse(&with_year("", 2021)).map(|config| config.year),
+            Ok(Some(2021))
+        );
+
+        let config = Config::parse(
+            "[paths]\ninputs = \"~/aoc/inputs\"\nsolutions = \"src/bin\"\n[site]\nbase_url = \"http://localhost:8080\"\ncontact = \"me@example.com\"\n[run]\nformat = \"json\"\nday_timeout = 30\ntimeout = 2.5\nmemory_limit = 512\n",
+        )
+        .unwrap();
+        assert_eq!(config.paths.inputs, Some(PathBuf::from("~/aoc/inputs")));
+        assert_eq!(
+            config
+                .site
+                .base_url
+                .as_deref(),
+            Some("http://localhost:8080")
+        );
+        assert_eq!(config.site.contact.as_deref(), Some("me@example.com"));
+        assert_eq!(
+            config.run,
+            Run {
+                format: Some(Format::Json),
+                timeout: Some(Duration::from_millis(2500)),
+                day_timeout: Some(Duration::from_secs(30)),
+                memory_limit: Some(512 * 1024 * 1024),
+            }
+        );
+
+        assert!(Config::parse("[paths]\nsolutions = \"solutions\"").is_err());
+        assert!(Config::parse("[run]\nformat = \"yaml\"").is_err());
+        assert!(Config::parse("[run]\ntimeout = 0").is_err());
+        assert_eq!(
+            Config::parse("[run]\nrelease = true"),
+            Err("unknown setting `run.release`".to_string())
+        );
+        assert_eq!(
+            resolve(Path::new("inputs")),
+            input::project_dir().join("inputs")
+        );
+    }
+}
@@ -0,0 +1,150 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use crate::answers::Answers;
+use crate::input;
+use crate::layout::{self, FRONT_MATTER_DELIMITER};
+use crate::Solution;
+use std::path::{Path, PathBuf};
+
+/// An example input, optionally preceded by its expected answers as TOML front matter:
+///
+/// ```text
+/// ---
+/// part_one = 24000
+/// ---
+/// 1000
+/// 2000
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Example {
+    /// The expected answers. Parts without one are not checked.
+    pub answers: Answers,
+    contents: String,
+    input_start: usize,
+}
+
+impl Example {
+    pub fn parse(contents: String) -> Result<Self, String> {
+        let (front_matter, input) = layout::split_front_matter(&contents).ok_or_else(|| {
+            format!("front matter opened with `{FRONT_MATTER_DELIMITER}` is never closed")
+        })?;
+        let answers = match front_matter {
+            Some(front_matter) => Answers::parse(front_matter)?,
+            None => Answers::default(),
+        };
+        let input_start = contents.len() - input.len();
+        Ok(Self {
+            answers,
+            contents,
+            input_start,
+        })
+    }
+
+    /// An example for `input`, with `answers` as front matter if there are any.
+    pub fn new(answers: Answers, input: &str) -> Self {
+        let front_matter = match answers == Answers::default() {
+            true => String::new(),
+            false => format!(
+                "{FRONT_MATTER_DELIMITER}\n{}{FRONT_MATTER_DELIMITER}\n",
+                answers.to_toml()
+            ),
+        };
+        Self {
+            answers,
+            input_start: front_matter.len(),
+            contents: front_matter + input,
+        }
+    }
+
+    pub fn read(path: &Path) -> Result<Self, String> {
+        let contents = input::read_path(path).map_err(|e| e.to_string())?;
+        Self::parse(contents).map_err(|e| format!("{}: {e}", path.display()))
+    }
+
+    /// The example input without its front matter.
+    pub fn input(&self) -> &str {
+        &self.contents[self.input_start..]
+    }
+
+    /// The whole file, which line numbers in errors refer to.
+    pub fn contents(&self) -> &str {
+        &self.contents
+    }
+}
+
+pub use crate::layout::is_example_for;
+
+/// All example files of `day`, `NN.txt` first and then `NN-<n>.txt` by number.
+pub fn paths(day: u8) -> Vec<PathBuf> {
+    layout::example_files(&input::folder_dir("examples"), day)
+}
+
+/// Runs `part` of `S` against the example at `path` and asserts that it produces the expected
+/// answer. Called by the tests that `example_tests!` generates.
+pub fn check<S: Solution>(path: &str, part: u8) {
+    crate::trace::set_day(S::DAY);
+    let example = Example::read(Path::new(path)).unwrap_or_else(|e| panic!("{e}"));
+    let expected = example
+        .answers
+        .get(part)
+        .unwrap_or_else(|| panic!("{path} has no expected answer for part {part}"));
+
+    let input = S::parse(example.input())
+        .unwrap_or_else(|e| panic!("{path}:\n{}", e.locate(example.contents())));
+    let answer = match part {
+        1 => S::part_one(&input).map(|answer| answer.to_string()),
+        _ => S::part_two(&input).map(|answer| answer.to_string()),
+    };
+
+    assert_eq!(answer.as_deref(), Some(expected), "part {part} of {path}");
+}
+
+/// Generates a test for every expected answer in the day's example files, which checks the
+/// answer of `$solution`.
+///
+/// `build.rs` writes the tests for each day's binary to `$OUT_DIR`, so adding an example file or
+/// an expected answer needs no changes to the solution itself. They are only included into test
+/// builds of the day's own binary, as other binaries that compile the day in have no tests for it.
+/// Parts that no example has an expected answer for, e.g. because the example file is still empty,
+/// get an ignored test that gives the reason.
+#[macro_export]
+macro_rules! example_tests {
+    ($solution:ident) => {
+        #[cfg(test)]
+        mod example_tests {
+            /// The solution that the generated tests check.
+            type Tested = super::$solution;
+
+            include!(concat!(
+                env!("OUT_DIR"),
+                "/example_tests/",
+                env!("CARGO_BIN_NAME"),
+                ".rs"
+            ));
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_front_matter() {
+        let example = Example::parse("---\npart_two = 12\n---\nA Y\nB X\n".to_string()).unwrap();
+        assert_eq!(example.answers.get(1), None);
+        assert_eq!(example.answers.get(2), Some("12"));
+        assert_eq!(example.input(), "A Y\nB X\n");
+
+        let plain = Example::parse("A Y\n".to_string()).unwrap();
+        assert_eq!(plain.answers, Answers::default());
+        assert_eq!(plain.input(), "A Y\n");
+
+        assert!(Example::parse("---\npart_one = 1\nA Y\n".to_string()).is_err());
+
+        let example = Example::new(example.answers.clone(), example.input());
+        assert_eq!(Example::parse(example.contents().to_string()), Ok(example));
+    }
+}
@@ -0,0 +1,342 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use crate::bench::BenchResult;
+use crate::input;
+use crate::output::Format;
+use crate::{ANSI_BOLD, ANSI_RESET};
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::PathBuf;
+use std::process::Command;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+const HISTORY_FILE: &str = ".bench-history.csv";
+const HEADER: &str =
+    "day,part,commit,profile,timestamp,samples,min_ns,median_ns,mean_ns,std_dev_ns";
+
+/// One benchmarked part, as stored in the history file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Entry {
+    pub day: u8,
+    pub part: u8,
+    /// Short hash of `HEAD`, suffixed with `-dirty` if the working tree had changes.
+    pub commit: String,
+    /// `debug` or `release`, see [`current_profile`]. Only runs of the same profile are compared.
+    pub profile: String,
+    /// Seconds since the unix epoch.
+    pub timestamp: u64,
+    pub samples: usize,
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub std_dev: Duration,
+}
+
+impl Entry {
+    fn to_csv(&self) -> String {
+        format!(
+            "{},{},{},{},{},{},{},{},{},{}",
+            self.day,
+            self.part,
+            self.commit,
+            self.profile,
+            self.timestamp,
+            self.samples,
+            self.min.as_nanos(),
+            self.median.as_nanos(),
+            self.mean.as_nanos(),
+            self.std_dev.as_nanos(),
+        )
+    }
+
+    fn from_csv(line: &str) -> Option<Self> {
+        let fields: Vec<&str> = line.split(',').collect();
+        if fields.len() != 10 {
+            return None;
+        }
+        let nanos = |i: usize| {
+            fields[i]
+                .parse()
+                .ok()
+                .map(Duration::from_nanos)
+        };
+        Some(Self {
+            day: fields[0].parse().ok()?,
+            part: fields[1].parse().ok()?,
+            commit: fields[2].to_string(),
+            profile: fields[3].to_string(),
+            timestamp: fields[4].parse().ok()?,
+            samples: fields[5].parse().ok()?,
+            min: nanos(6)?,
+            median: nanos(7)?,
+            mean: nanos(8)?,
+            std_dev: nanos(9)?,
+        })
+    }
+}
+
+/// How `--compare` picks a baseline and decides what counts as slower.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CompareOptions {
+    /// Only compare against entries whose commit starts with this. Defaults to the latest entry.
+    pub baseline: Option<String>,
+    /// Allowed slowdown of the median, in percent.
+    pub threshold: f64,
+}
+
+impl Default for CompareOptions {
+    fn default() -> Self {
+        Self {
+            baseline: None,
+            threshold: 5.0,
+        }
+    }
+}
+
+/// The result of comparing a fresh benchmark of one part against its baseline.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Comparison {
+    pub part: u8,
+    pub baseline: Entry,
+    pub median: Duration,
+    /// Relative change of the median, in percent. Positive means slower.
+    pub change: f64,
+    pub regressed: bool,
+}
+
+/// `.bench-history.csv`, or e.g. `.bench-history-2021.csv` for years other than the default.
+fn history_path() -> PathBuf {
+    match input::year() {
+        year if year == input::default_year() => input::project_dir().join(HISTORY_FILE),
+        year => input::project_dir().join(HISTORY_FILE.replace(".csv", &format!("-{year}.csv"))),
+    }
+}
+
+/// Runs git in the repository, wherever the command was started from.
+fn git(args: &[&str]) -> Option<String> {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(input::project_dir())
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    Some(
+        String::from_utf8_lossy(&output.stdout)
+            .trim()
+            .to_string(),
+    )
+}
+
+/// Identifies the code that was benchmarked, e.g. `1a2b3c4` or `1a2b3c4-dirty`.
+pub fn current_commit() -> String {
+    match git(&["rev-parse", "--short", "HEAD"]) {
+        Some(hash) => match git(&["status", "--porcelain"]) {
+            Some(status) if !status.is_empty() => format!("{hash}-dirty"),
+            _ => hash,
+        },
+        None => "unknown".to_string(),
+    }
+}
+
+/// The profile this code was built with. Debug builds are many times slower, so their timings
+/// are not compared against those of release builds.
+pub fn current_profile() -> &'static str {
+    match cfg!(debug_assertions) {
+        true => "debug",
+        false => "release",
+    }
+}
+
+/// Turns the solved parts of a benchmark into history entries.
+pub fn entries(day: u8, commit: &str, results: &[BenchResult]) -> Vec<Entry> {
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default();
+
+    results
+        .iter()
+        .filter_map(|result| {
+            let stats = result.stats.as_ref()?;
+            Some(Entry {
+                day,
+                part: result.part,
+                commit: commit.to_string(),
+                profile: current_profile().to_string(),
+                timestamp,
+                samples: stats.samples,
+                min: stats.min,
+                median: stats.median,
+                mean: stats.mean,
+                std_dev: stats.std_dev,
+            })
+        })
+        .collect()
+}
+
+/// Reads all entries from the history file. A missing file is an empty history.
+pub fn load() -> io::Result<Vec<Entry>> {
+    match fs::read_to_string(history_path()) {
+        Ok(contents) => Ok(contents
+            .lines()
+            .filter_map(Entry::from_csv)
+            .collect()),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(vec![]),
+        Err(e) => Err(e),
+    }
+}
+
+/// Appends `entries` to the history file, creating it with a header if needed.
+pub fn append(entries: &[Entry]) -> io::Result<()> {
+    let path = history_path();
+    let is_new = !path.exists();
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)?;
+    if is_new {
+        writeln!(file, "{HEADER}")?;
+    }
+    for entry in entries {
+        writeln!(file, "{}", entry.to_csv())?;
+    }
+    Ok(())
+}
+
+/// Compares `current` against the latest matching entry for the same day, part and profile in
+/// `history`.
+pub fn compare(history: &[Entry], current: &[Entry], options: &CompareOptions) -> Vec<Comparison> {
+    current
+        .iter()
+        .filter_map(|entry| {
+            let baseline = history
+                .iter()
+                .rev()
+                .find(|candidate| {
+                    candidate.day == entry.day
+                        && candidate.part == entry.part
+                        && candidate.profile == entry.profile
+                        && options
+                            .baseline
+                            .as_ref()
+                            .is_none_or(|commit| {
+                                candidate
+                                    .commit
+                                    .starts_with(commit.as_str())
+                            })
+                })?;
+
+            let before = baseline.median.as_nanos() as f64;
+            let after = entry.median.as_nanos() as f64;
+            let change = if before > 0.0 {
+                (after - before) / before * 100.0
+            } else {
+                0.0
+            };
+
+            Some(Comparison {
+                part: entry.part,
+                baseline: baseline.clone(),
+                median: entry.median,
+                change,
+                regressed: change > options.threshold,
+            })
+        })
+        .collect()
+}
+
+/// Prints comparisons. Structured formats keep stdout parseable, so the report goes to stderr.
+pub fn print_comparisons(format: Format, comparisons: &[Comparison], options: &CompareOptions) {
+    let lines: Vec<String> = comparisons
+        .iter()
+        .map(|c| {
+            let verdict = if c.regressed {
+                format!(
+                    "{ANSI_BOLD}slower than the {}% threshold{ANSI_RESET}",
+                    options.threshold
+                )
+            } else {
+                "ok".to_string()
+            };
+            format!(
+                "Part {}: median {:.2?} vs {:.2?} at {} ({:+.1}%), {verdict}",
+                c.part, c.median, c.baseline.median, c.baseline.commit, c.change
+            )
+        })
+        .collect();
+
+    let lines = if lines.is_empty() {
+        vec!["No baseline to compare against.".to_string()]
+    } else {
+        lines
+    };
+
+    for line in lines {
+        match format {
+            Format::Text => println!("{line}"),
+            _ => eprintln!("{line}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(part: u8, commit: &str, median: u64) -> Entry {
+        Entry {
+            day: 11,
+            part,
+            commit: commit.to_string(),
+            profile: "release".to_string(),
+            timestamp: 1_670_000_000,
+            samples: 100,
+            min: Duration::from_nanos(median - 10),
+            median: Duration::from_nanos(median),
+            mean: Duration::from_nanos(median),
+            std_dev: Duration::from_nanos(5),
+        }
+    }
+
+    #[test]
+    fn test_csv_roundtrip() {
+        let original = entry(2, "1a2b3c4-dirty", 1_000);
+        assert_eq!(Entry::from_csv(&original.to_csv()), Some(original));
+        assert_eq!(Entry::from_csv(HEADER), None);
+    }
+
+    #[test]
+    fn test_compare() {
+        let history = vec![
+            entry(1, "aaaaaaa", 1_000),
+            entry(1, "bbbbbbb", 2_000),
+            entry(2, "aaaaaaa", 1_000),
+            Entry {
+                profile: "debug".to_string(),
+                ..entry(2, "bbbbbbb", 30_000)
+            },
+        ];
+        let current = vec![entry(1, "ccccccc", 1_100), entry(2, "ccccccc", 1_020)];
+
+        let latest = compare(&history, &current, &CompareOptions::default());
+        assert_eq!(latest.len(), 2);
+        assert_eq!(latest[0].baseline.commit, "bbbbbbb");
+        assert!(!latest[0].regressed);
+        // The later debug run of part 2 is no baseline for a release run.
+        assert_eq!(latest[1].baseline.commit, "aaaaaaa");
+        assert!(!latest[1].regressed);
+
+        let pinned = CompareOptions {
+            baseline: Some("aaa".to_string()),
+            threshold: 5.0,
+        };
+        let against_a = compare(&history, &current, &pinned);
+        assert!(against_a[0].regressed);
+        assert!((against_a[0].change - 10.0).abs() < 1e-9);
+        assert!(!against_a[1].regressed);
+    }
+}
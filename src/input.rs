@@ -0,0 +1,241 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use crate::examples::{self, Example};
+use crate::{cli, client, config, layout};
+use std::env;
+use std::fs;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicI32, Ordering};
+
+/// Overrides the directory puzzle inputs are read from, e.g. to keep them outside the repository.
+pub const INPUTS_DIR_VAR: &str = "AOC_INPUTS_DIR";
+
+/// Where a day's puzzle input is read from.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum InputSource {
+    /// `src/inputs/NN.txt`, or `NN.txt` in `$AOC_INPUTS_DIR` if that is set.
+    #[default]
+    Puzzle,
+    /// The first example of the day, `src/examples/NN.txt` or `src/examples/NN-1.txt`.
+    Example,
+    /// A file given on the command line.
+    File(PathBuf),
+    /// Standard input.
+    Stdin,
+}
+
+/// The root of this repository. Paths are resolved against it rather than against the working
+/// directory, so binaries can be run from anywhere.
+pub fn project_dir() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+}
+
+static YEAR: AtomicI32 = AtomicI32::new(0);
+
+/// The year set in `aoc.toml`. Its days use the flat layout, e.g. `src/bin/NN.rs` and
+/// `src/inputs/NN.txt`. There is no fallback, as one that moves on with the calendar would file
+/// answers, checksums and benchmarks of the flat layout under another year every December.
+pub fn default_year() -> i32 {
+    config::get()
+        .year
+        .unwrap_or_else(|| {
+            cli::fail(
+                cli::Exit::Environment,
+                format!(
+                    "\"{}\" sets no `year`. Add e.g. `year = {}`, or run `cargo aoc scaffold <day>` to set it to the latest event.",
+                    config::config_path().display(),
+                    client::latest_year()
+                ),
+            )
+        })
+}
+
+/// Sets the year that paths and days are resolved for, e.g. from `--year`.
+pub fn set_year(year: i32) {
+    YEAR.store(year, Ordering::Relaxed);
+}
+
+/// The year that paths and days are resolved for. Without a call to [`set_year`], the default year.
+pub fn year() -> i32 {
+    match YEAR.load(Ordering::Relaxed) {
+        0 => default_year(),
+        year => year,
+    }
+}
+
+/// The directory holding the files of `folder`, e.g. `src/examples`. Years other than the default
+/// one have a directory of their own in it, e.g. `src/examples/2021`.
+///
+/// The inputs and examples can be moved in `aoc.toml`, and [`INPUTS_DIR_VAR`] overrides that.
+pub fn folder_dir(folder: &str) -> PathBuf {
+    let paths = &config::get().paths;
+    let configured = match folder {
+        "inputs" => paths.inputs.as_deref(),
+        "examples" => paths.examples.as_deref(),
+        _ => None,
+    };
+    let dir = match (env::var_os(INPUTS_DIR_VAR), configured) {
+        (Some(dir), _) if folder == "inputs" && !dir.is_empty() => PathBuf::from(dir),
+        (_, configured) => layout::folder_dir(&project_dir(), folder, configured),
+    };
+    match year() {
+        year if year == default_year() => dir,
+        year => dir.join(year.to_string()),
+    }
+}
+
+/// The name of the binary that solves `day`, `NN` or `YYYY-NN` for years other than the default.
+pub fn bin_name(day: u8) -> String {
+    match year() {
+        year if year == default_year() => format!("{day:02}"),
+        year => format!("{year}-{day:02}"),
+    }
+}
+
+pub use crate::layout::parse_bin_name;
+
+/// The options that select the current year on the command line, empty for the default year.
+pub fn year_args() -> Vec<String> {
+    match year() {
+        year if year == default_year() => vec![],
+        year => vec!["--year".to_string(), year.to_string()],
+    }
+}
+
+pub fn file_path(folder: &str, day: u8) -> PathBuf {
+    folder_dir(folder).join(format!("{day:02}.txt"))
+}
+
+/// Reads a file, naming it in the error if that fails.
+pub fn read_path(path: &Path) -> io::Result<String> {
+    fs::read_to_string(path).map_err(|e| {
+        io::Error::new(
+            e.kind(),
+            format!("could not read \"{}\": {e}", path.display()),
+        )
+    })
+}
+
+/// Writes `contents` to `path` by way of a temporary file next to it, so that readers and
+/// concurrent writers never see a half-written file. Creates missing directories.
+pub fn write_file(path: &Path, contents: &str) -> io::Result<()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let file_name = path
+        .file_name()
+        .unwrap_or_default()
+        .to_string_lossy();
+    let tmp_path = path.with_file_name(format!(".{file_name}.{}.tmp", std::process::id()));
+    fs::write(&tmp_path, contents)
+        .and_then(|_| fs::rename(&tmp_path, path))
+        .map_err(|e| {
+            let _ = fs::remove_file(&tmp_path);
+            io::Error::new(
+                e.kind(),
+                format!("could not write \"{}\": {e}", path.display()),
+            )
+        })
+}
+
+impl InputSource {
+    /// The file this source reads for `day`, if it reads a file.
+    pub fn path(&self, day: u8) -> Option<PathBuf> {
+        match self {
+            InputSource::Puzzle => Some(file_path("inputs", day)),
+            InputSource::Example => Some(
+                examples::paths(day)
+                    .into_iter()
+                    .next()
+                    .unwrap_or_else(|| file_path("examples", day)),
+            ),
+            InputSource::File(path) => Some(path.clone()),
+            InputSource::Stdin => None,
+        }
+    }
+
+    /// Reads the input for `day`. A missing or empty input is an error that names where the input
+    /// was expected.
+    pub fn read(&self, day: u8) -> io::Result<String> {
+        let input = match self.path(day) {
+            Some(path) if *self == InputSource::Example => Example::read(&path)
+                .map(|example| example.input().to_string())
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?,
+            Some(path) => read_path(&path).map_err(|e| match (self, e.kind()) {
+                (InputSource::Puzzle, io::ErrorKind::NotFound) => io::Error::new(
+                    e.kind(),
+                    format!(
+                        "{e}. Run `cargo download {}`.",
+                        [day.to_string()]
+                            .into_iter()
+                            .chain(year_args())
+                            .collect::<Vec<_>>()
+                            .join(" ")
+                    ),
+                ),
+                _ => e,
+            })?,
+            None => {
+                let mut input = String::new();
+                io::stdin().read_to_string(&mut input)?;
+                input
+            }
+        };
+
+        if input.trim().is_empty() {
+            let source = match self.path(day) {
+                Some(path) => format!("\"{}\"", path.display()),
+                None => "stdin".to_string(),
+            };
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("the input from {source} is empty"),
+            ));
+        }
+
+        Ok(input)
+    }
+
+    /// Whether this is the day's real puzzle input, which accepted answers and benchmark history
+    /// refer to.
+    pub fn is_puzzle(&self) -> bool {
+        *self == InputSource::Puzzle
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read() {
+        let path = env::temp_dir().join(format!("aoc-input-{}.txt", std::process::id()));
+
+        let missing = InputSource::File(path.clone())
+            .read(1)
+            .unwrap_err();
+        assert_eq!(missing.kind(), io::ErrorKind::NotFound);
+        assert!(missing
+            .to_string()
+            .contains(&path.display().to_string()));
+
+        fs::write(&path, "\n").unwrap();
+        let empty = InputSource::File(path.clone())
+            .read(1)
+            .unwrap_err();
+        assert_eq!(empty.kind(), io::ErrorKind::InvalidData);
+
+        fs::write(&path, "1\n2\n").unwrap();
+        assert_eq!(
+            InputSource::File(path.clone())
+                .read(1)
+                .unwrap(),
+            "1\n2\n"
+        );
+
+        fs::remove_file(path).unwrap();
+    }
+}
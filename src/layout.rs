@@ -0,0 +1,327 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+//! How days, examples and configured paths are laid out in the repository.
+//!
+//! `build.rs` includes this file with `#[path]`, so it may only use `std` and `toml`.
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// The days that rustc rejects, as `<binary>\t<error>` lines. `cargo aoc all` and `cargo aoc submit`
+/// check each day on its own and pass the errors to `build.rs` in this variable, which leaves
+/// these days out of the registry so that the others still compile.
+pub const COMPILE_ERRORS_VAR: &str = "AOC_COMPILE_ERRORS";
+
+/// Opens and closes the front matter of an example.
+pub const FRONT_MATTER_DELIMITER: &str = "---";
+
+/// Reads the year and day from a binary name like `NN` or `YYYY-NN`. There is no year for `NN`.
+pub fn parse_bin_name(name: &str) -> Option<(Option<i32>, u8)> {
+    let is_number = |s: &str| {
+        !s.is_empty()
+            && s.bytes()
+                .all(|b| b.is_ascii_digit())
+    };
+    match name.split_once('-') {
+        Some((year, day))
+            if year.len() == 4 && is_number(year) && day.len() == 2 && is_number(day) =>
+        {
+            Some((Some(year.parse().ok()?), day.parse().ok()?))
+        }
+        None if name.len() == 2 && is_number(name) => Some((None, name.parse().ok()?)),
+        _ => None,
+    }
+}
+
+/// Resolves a configured path: `~/` is the home directory, and relative paths are relative to
+/// `root`.
+pub fn resolve(root: &Path, path: &Path) -> PathBuf {
+    match (path.strip_prefix("~"), env::var_os("HOME")) {
+        (Ok(rest), Some(home)) => PathBuf::from(home).join(rest),
+        _ => root.join(path),
+    }
+}
+
+/// The directory holding the files of `folder` of the default year, e.g. `src/examples`, unless
+/// `configured` moves it.
+pub fn folder_dir(root: &Path, folder: &str, configured: Option<&Path>) -> PathBuf {
+    match configured {
+        Some(dir) => resolve(root, dir),
+        None => root.join("src").join(folder),
+    }
+}
+
+/// The name of an example file of `day` that tests are named after: `example` for `NN.txt` and
+/// `example_<n>` for `NN-<n>.txt`. `None` for other files.
+pub fn example_name(file_name: &str, day: u8) -> Option<String> {
+    let stem = file_name.strip_suffix(".txt")?;
+    if stem == format!("{day:02}") {
+        return Some("example".to_string());
+    }
+    let number = stem.strip_prefix(&format!("{day:02}-"))?;
+    if number.is_empty()
+        || !number
+            .bytes()
+            .all(|b| b.is_ascii_digit())
+    {
+        return None;
+    }
+    Some(format!("example_{number}"))
+}
+
+/// Whether `name` is an example file for `day`: `NN.txt` or `NN-<n>.txt`.
+pub fn is_example_for(name: &str, day: u8) -> bool {
+    example_name(name, day).is_some()
+}
+
+/// All example files of `day` in `dir`, `NN.txt` first and then `NN-<n>.txt` by number.
+pub fn example_files(dir: &Path, day: u8) -> Vec<PathBuf> {
+    let mut paths: Vec<PathBuf> = fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .filter_map(|entry| entry.ok())
+                .map(|entry| entry.path())
+                .filter(|path| {
+                    path.file_name()
+                        .and_then(|name| name.to_str())
+                        .is_some_and(|name| is_example_for(name, day))
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    paths.sort_by_key(|path| {
+        let stem = path
+            .file_stem()
+            .unwrap_or_default()
+            .to_string_lossy()
+            .to_string();
+        let number: u32 = stem
+            .split_once('-')
+            .and_then(|(_, number)| number.parse().ok())
+            .unwrap_or(0);
+        (number, stem)
+    });
+    paths
+}
+
+/// Splits an example into its front matter, if it has any, and its input. `None` if the front
+/// matter is never closed.
+pub fn split_front_matter(contents: &str) -> Option<(Option<&str>, &str)> {
+    let mut lines = contents.split_inclusive('\n');
+    let opening = match lines.next() {
+        Some(line) if line.trim_end() == FRONT_MATTER_DELIMITER => line.len(),
+        _ => return Some((None, contents)),
+    };
+    let mut offset = opening;
+    for line in lines {
+        if line.trim_end() == FRONT_MATTER_DELIMITER {
+            return Some((
+                Some(&contents[opening..offset]),
+                &contents[offset + line.len()..],
+            ));
+        }
+        offset += line.len();
+    }
+    None
+}
+
+/// Whether an example has any input after its front matter. An example whose front matter is
+/// never closed counts as having input, so that its tests fail with the error.
+pub fn has_input(contents: &str) -> bool {
+    split_front_matter(contents).is_none_or(|(_, input)| !input.trim().is_empty())
+}
+
+/// The parts an example has expected answers for. Both parts get a test if the front matter
+/// cannot be parsed, so that the tests fail with the error instead of silently checking nothing.
+pub fn expected_parts(contents: &str) -> Vec<u8> {
+    let front_matter = match split_front_matter(contents) {
+        Some((front_matter, _)) => front_matter.unwrap_or_default(),
+        None => return vec![1, 2],
+    };
+    match toml::from_str::<toml::value::Table>(front_matter) {
+        Ok(answers) => [(1, "part_one"), (2, "part_two")]
+            .into_iter()
+            .filter(|(_, key)| answers.contains_key(*key))
+            .map(|(part, _)| part)
+            .collect(),
+        Err(_) => vec![1, 2],
+    }
+}
+
+/// Generates the tests that `advent_of_code::example_tests!` includes into a solution, for the
+/// examples of `day` in `examples_dir`. Paths in messages are shown relative to `root`.
+///
+/// A part that no example has an expected answer for still gets a test, which is ignored with the
+/// reason, so that a freshly scaffolded day passes but `cargo test` points out the missing work.
+/// So does every empty example file, named after the file.
+pub fn example_tests(root: &Path, examples_dir: &Path, year: Option<i32>, day: u8) -> String {
+    let examples = example_files(examples_dir, day);
+
+    let relative = |path: &Path| {
+        path.strip_prefix(root)
+            .unwrap_or(path)
+            .display()
+            .to_string()
+    };
+    let first = examples_dir.join(format!("{day:02}.txt"));
+    let year_arg = year
+        .map(|year| format!(" --year {year}"))
+        .unwrap_or_default();
+    let fix = format!("paste the example from the puzzle into it, or run `cargo aoc scaffold {day}{year_arg} --fetch`");
+    let missing = match examples.is_empty() {
+        true => Some(format!("{} is missing, {fix}", relative(&first))),
+        false => None,
+    };
+
+    let mut tests = vec![];
+    let mut covered = [false; 2];
+    for path in examples {
+        let name = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .and_then(|name| example_name(name, day))
+            .unwrap_or_default();
+        let contents = fs::read_to_string(&path).unwrap_or_default();
+        if !has_input(&contents) {
+            let reason = format!("{} is empty, {fix}", relative(&path));
+            tests.push(format!(
+                "    #[test]\n    #[ignore = {reason:?}]\n    fn {name}() {{}}\n"
+            ));
+            continue;
+        }
+        for part in expected_parts(&contents) {
+            let part_name = if part == 1 { "part_one" } else { "part_two" };
+            covered[usize::from(part) - 1] = true;
+            tests.push(format!(
+                "    #[test]\n    fn {part_name}_{name}() {{\n        advent_of_code::examples::check::<Tested>({:?}, {part});\n    }}\n",
+                path.display().to_string()
+            ));
+        }
+    }
+
+    for (part_name, covered) in ["part_one", "part_two"]
+        .into_iter()
+        .zip(covered)
+    {
+        if covered {
+            continue;
+        }
+        let reason = missing.clone().unwrap_or_else(|| {
+            format!(
+                "no example has an expected answer for {}, add `{part_name} = <answer>` to the front matter of {}",
+                part_name.replace('_', " "),
+                relative(&first)
+            )
+        });
+        tests.push(format!(
+            "    #[test]\n    #[ignore = {reason:?}]\n    fn {part_name}_example() {{}}\n"
+        ));
+    }
+
+    tests.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::process;
+
+    #[test]
+    fn test_parse_bin_name() {
+        assert_eq!(parse_bin_name("07"), Some((None, 7)));
+        assert_eq!(parse_bin_name("2021-25"), Some((Some(2021), 25)));
+        assert_eq!(parse_bin_name("7"), None);
+        assert_eq!(parse_bin_name("2021-7"), None);
+        assert_eq!(parse_bin_name("scaffold"), None);
+    }
+
+    #[test]
+    fn test_paths() {
+        let root = Path::new("/repo");
+        assert_eq!(resolve(root, Path::new("inputs")), root.join("inputs"));
+        assert_eq!(
+            folder_dir(root, "examples", None),
+            root.join("src/examples")
+        );
+        assert_eq!(
+            folder_dir(root, "examples", Some(Path::new("/elsewhere"))),
+            PathBuf::from("/elsewhere")
+        );
+    }
+
+    #[test]
+    fn test_example_name() {
+        assert_eq!(example_name("06.txt", 6).as_deref(), Some("example"));
+        assert_eq!(example_name("06-12.txt", 6).as_deref(), Some("example_12"));
+        assert!(is_example_for("06.txt", 6));
+        assert!(!is_example_for("16.txt", 6));
+        assert!(!is_example_for("06-.txt", 6));
+        assert!(!is_example_for("06-a.txt", 6));
+        assert!(!is_example_for("06.md", 6));
+    }
+
+    #[test]
+    fn test_front_matter() {
+        let example = "---\npart_two = 12\n---\nA Y\n";
+        assert_eq!(
+            split_front_matter(example),
+            Some((Some("part_two = 12\n"), "A Y\n"))
+        );
+        assert_eq!(split_front_matter("A Y\n"), Some((None, "A Y\n")));
+        assert_eq!(split_front_matter("---\npart_one = 1\nA Y\n"), None);
+
+        assert!(has_input(example));
+        assert!(!has_input("---\npart_one = 1\n---\n\n"));
+        assert!(!has_input(""));
+        assert!(has_input("---\npart_one = 1\n"));
+
+        assert_eq!(expected_parts(example), vec![2]);
+        assert_eq!(expected_parts("A Y\n"), Vec::<u8>::new());
+        assert_eq!(expected_parts("---\npart_one = \n---\nA Y\n"), vec![1, 2]);
+        assert_eq!(expected_parts("---\npart_one = 1\nA Y\n"), vec![1, 2]);
+    }
+
+    #[test]
+    fn test_example_tests() {
+        let dir = env::temp_dir().join(format!("aoc-layout-{}", process::id()));
+        fs::create_dir_all(&dir).unwrap();
+
+        let tests = example_tests(&dir, &dir, Some(2021), 3);
+        assert!(tests.contains("#[ignore = \"03.txt is missing, paste the example from the puzzle into it, or run `cargo aoc scaffold 3 --year 2021 --fetch`\"]"));
+
+        fs::write(dir.join("03.txt"), "---\npart_one = 1\n---\nA\n").unwrap();
+        fs::write(dir.join("03-10.txt"), "B\n").unwrap();
+        fs::write(dir.join("03-2.txt"), "---\npart_two = 2\n---\nC\n").unwrap();
+        assert_eq!(
+            example_files(&dir, 3)
+                .iter()
+                .map(|path| path.file_name().unwrap())
+                .collect::<Vec<_>>(),
+            ["03.txt", "03-2.txt", "03-10.txt"]
+        );
+        let tests = example_tests(&dir, &dir, None, 3);
+        assert!(tests.contains("fn part_one_example() {"));
+        assert!(tests.contains("fn part_two_example_2() {"));
+        assert!(!tests.contains("#[ignore"));
+
+        // Every empty example gets a test of its own, even if the others cover both parts.
+        fs::write(dir.join("03-4.txt"), "---\npart_one = 4\n---\n\n").unwrap();
+        fs::write(dir.join("03-5.txt"), "").unwrap();
+        let tests = example_tests(&dir, &dir, None, 3);
+        for name in ["03-4.txt", "03-5.txt"] {
+            let number = &name[3..4];
+            assert!(tests.contains(&format!("    #[ignore = \"{name} is empty, paste the example from the puzzle into it, or run `cargo aoc scaffold 3 --fetch`\"]\n    fn example_{number}() {{}}")));
+        }
+        assert_eq!(
+            tests
+                .matches("#[ignore")
+                .count(),
+            2
+        );
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -3,123 +3,620 @@
  * There is no need to edit this file unless you want to change template functionality.
  * Prefer `./helpers.rs` if you want to extract code from your solutions.
  */
-use std::env;
-use std::fs;
+use std::any::Any;
+use std::fmt::Display;
+use std::io;
+use std::panic::{self, AssertUnwindSafe};
+use std::time::{Duration, Instant};
 
+pub mod answers;
+pub mod args;
+pub mod bench;
+pub mod checksum;
+pub mod cli;
+pub mod client;
+pub mod config;
+pub mod examples;
 pub mod helpers;
+pub mod history;
+pub mod input;
+pub mod layout;
+pub mod memory;
+pub mod output;
+pub mod parse;
+pub mod puzzle;
+pub mod submissions;
+pub mod template;
+pub mod trace;
+pub mod worker;
+
+pub use parse::ParseError;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
 pub const ANSI_RESET: &str = "\x1b[0m";
 
-#[macro_export]
-macro_rules! solve {
-    ($part:expr, $solver:ident, $input:expr) => {{
-        use advent_of_code::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
-        use std::fmt::Display;
-        use std::time::Instant;
-
-        fn print_result<T, F>(func: F, input: &str)
-            where
-                T: Display,
-                F: FnOnce(&str) -> Option<T>,
+/// A solution for a single day of the advent.
+///
+/// The raw puzzle input is parsed once by [`Solution::parse`] and the result is shared by both
+/// parts, so neither part needs to re-split the input on its own.
+pub trait Solution {
+    /// The day of the advent this solution belongs to.
+    const DAY: u8;
+
+    /// The parsed puzzle input that is handed to both parts.
+    type Input;
+    type PartOne: Display;
+    type PartTwo: Display;
+
+    /// Turns the raw input into [`Solution::Input`]. Problems with the input should be reported
+    /// through [`ParseError`] rather than by panicking, so the runner can point at the bad text.
+    fn parse(input: &str) -> Result<Self::Input, ParseError>;
+    fn part_one(input: &Self::Input) -> Option<Self::PartOne>;
+    fn part_two(input: &Self::Input) -> Option<Self::PartTwo>;
+}
+
+/// How running one part of a day went.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Status {
+    /// The part returned an answer.
+    Solved,
+    /// The part returned `None`.
+    Unsolved,
+    /// Parsing the input or solving the part panicked with the given message.
+    Panicked(String),
+    /// The input could not be parsed.
+    Invalid(ParseError),
+    /// There is no solution for the day in `src/bin`.
+    Missing,
+    /// The input file for the day could not be read.
+    NoInput(String),
+    /// The solution for the day could not be compiled into the runner.
+    CompileError(String),
+    /// The part, or the day as a whole, ran longer than the given limit and was stopped.
+    TimedOut(Duration),
+    /// The day needed more heap memory than the given number of bytes and was stopped.
+    OutOfMemory(usize),
+}
+
+impl Status {
+    /// A stable, machine-readable name for the status.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Status::Solved => "solved",
+            Status::Unsolved => "unsolved",
+            Status::Panicked(_) => "panicked",
+            Status::Invalid(_) => "invalid_input",
+            Status::Missing => "missing",
+            Status::NoInput(_) => "no_input",
+            Status::CompileError(_) => "compile_error",
+            Status::TimedOut(_) => "timed_out",
+            Status::OutOfMemory(_) => "out_of_memory",
+        }
+    }
+
+    pub fn message(&self) -> Option<String> {
+        match self {
+            Status::Panicked(message) | Status::NoInput(message) | Status::CompileError(message) => {
+                Some(message.clone())
+            }
+            Status::Invalid(error) => Some(error.summary()),
+            Status::TimedOut(limit) => Some(format!("exceeded the time limit of {limit:.2?}")),
+            Status::OutOfMemory(limit) => Some(format!(
+                "exceeded the memory limit of {}",
+                memory::format_bytes(*limit)
+            )),
+            _ => None,
+        }
+    }
+
+    /// Whether the part went wrong, as opposed to being solved, unsolved or missing.
+    pub fn is_failure(&self) -> bool {
+        !matches!(self, Status::Solved | Status::Unsolved | Status::Missing)
+    }
+}
+
+/// The outcome of running one part of a solution.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PartResult {
+    pub part: u8,
+    pub status: Status,
+    pub answer: Option<String>,
+    pub elapsed: Duration,
+    /// Heap usage while solving the part, if the `memory` feature is enabled.
+    pub memory: Option<memory::MemoryStats>,
+    /// Set by [`answers::verify`]. Answers to other inputs than the puzzle input are not checked.
+    pub verdict: Option<answers::Verdict>,
+}
+
+impl PartResult {
+    /// A result for a part that did not get to run at all.
+    pub fn failed(part: u8, status: Status) -> Self {
+        Self {
+            part,
+            status,
+            answer: None,
+            elapsed: Duration::ZERO,
+            memory: None,
+            verdict: None,
+        }
+    }
+}
+
+/// The outcome of running both parts of a day.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DayResult {
+    /// Time spent in [`Solution::parse`], zero if the input was never parsed.
+    pub parse_elapsed: Duration,
+    pub parts: [PartResult; 2],
+}
+
+impl DayResult {
+    /// A result for a day whose parts did not get to run at all.
+    pub fn failed(status: Status) -> Self {
+        Self {
+            parse_elapsed: Duration::ZERO,
+            parts: [1, 2].map(|part| PartResult::failed(part, status.clone())),
+        }
+    }
+
+    /// Whether any part went wrong or gave another answer than the accepted one.
+    pub fn has_failures(&self) -> bool {
+        self.parts.iter().any(|part| {
+            part.status.is_failure() || matches!(part.verdict, Some(answers::Verdict::Wrong(_)))
+        })
+    }
+
+    /// How a single day's run ends: [`cli::Exit::Environment`] if its input could not be read,
+    /// as there was nothing to solve, and [`cli::Exit::Failure`] if any part went wrong.
+    pub fn exit_code(&self) -> cli::Exit {
+        if self
+            .parts
+            .iter()
+            .any(|part| matches!(part.status, Status::NoInput(_)))
         {
-            let timer = Instant::now();
-            let result = func(input);
-            let elapsed = timer.elapsed();
-            match result {
-                Some(result) => {
-                    println!("{result} {ANSI_ITALIC}(elapsed: {elapsed:.2?}){ANSI_RESET}");
+            cli::Exit::Environment
+        } else if self.has_failures() {
+            cli::Exit::Failure
+        } else {
+            cli::Exit::Success
+        }
+    }
+
+    /// The time spent parsing plus the time spent in solved parts.
+    pub fn elapsed(&self) -> Duration {
+        self.parse_elapsed
+            + self
+                .parts
+                .iter()
+                .filter(|part| part.status == Status::Solved)
+                .map(|part| part.elapsed)
+                .sum::<Duration>()
+    }
+}
+
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    match payload.downcast::<String>() {
+        Ok(message) => *message,
+        Err(payload) => match payload.downcast::<&str>() {
+            Ok(message) => message.to_string(),
+            Err(_) => "unknown panic payload".to_string(),
+        },
+    }
+}
+
+/// Calls `f`, turning a panic into an `Err` carrying the panic message.
+pub fn catch_panic<T>(f: impl FnOnce() -> T) -> Result<T, String> {
+    panic::catch_unwind(AssertUnwindSafe(f)).map_err(panic_message)
+}
+
+fn time_part<T: Display>(part: u8, solver: impl FnOnce() -> Option<T>) -> PartResult {
+    let ((answer, elapsed), memory) = memory::measure(|| {
+        let timer = Instant::now();
+        let answer = catch_panic(solver);
+        (answer, timer.elapsed())
+    });
+    match answer {
+        Ok(Some(answer)) => PartResult {
+            part,
+            status: Status::Solved,
+            answer: Some(answer.to_string()),
+            elapsed,
+            memory,
+            verdict: None,
+        },
+        Ok(None) => PartResult {
+            part,
+            status: Status::Unsolved,
+            answer: None,
+            elapsed,
+            memory,
+            verdict: None,
+        },
+        Err(message) => PartResult {
+            part,
+            status: Status::Panicked(message),
+            answer: None,
+            elapsed,
+            memory,
+            verdict: None,
+        },
+    }
+}
+
+/// A phase of [`run_observed`] that just finished.
+#[derive(Debug)]
+pub enum Progress<'a> {
+    /// The input was parsed successfully in the given time.
+    Parsed(Duration),
+    /// A part finished. If parsing failed, both parts are reported as failed right away.
+    Part(&'a PartResult),
+}
+
+/// Receives the [`Progress`] of [`run_observed`].
+pub type Observer<'a> = dyn FnMut(Progress) + 'a;
+
+/// Parses `input` and runs both parts of `S` against it, timing each phase separately.
+///
+/// Panics are caught and reported through [`Status::Panicked`], parse errors through
+/// [`Status::Invalid`]. If parsing fails, both parts fail with it.
+pub fn run<S: Solution>(raw: &str) -> DayResult {
+    run_observed::<S>(raw, &mut |_| {})
+}
+
+/// Like [`run`], but reports each phase to `observer` as soon as it finishes.
+pub fn run_observed<S: Solution>(raw: &str, observer: &mut Observer) -> DayResult {
+    trace::set_day(S::DAY);
+    let timer = Instant::now();
+    let parsed = catch_panic(|| S::parse(raw));
+    let parse_elapsed = timer.elapsed();
+
+    let status = match parsed {
+        Ok(Ok(input)) => {
+            observer(Progress::Parsed(parse_elapsed));
+            let part_one = time_part(1, || S::part_one(&input));
+            observer(Progress::Part(&part_one));
+            let part_two = time_part(2, || S::part_two(&input));
+            observer(Progress::Part(&part_two));
+            return DayResult {
+                parse_elapsed,
+                parts: [part_one, part_two],
+            };
+        }
+        Ok(Err(error)) => Status::Invalid(error.locate(raw)),
+        Err(message) => Status::Panicked(message),
+    };
+    let result = DayResult {
+        parse_elapsed,
+        ..DayResult::failed(status)
+    };
+    result
+        .parts
+        .iter()
+        .for_each(|part| observer(Progress::Part(part)));
+    result
+}
+
+/// Reads the input for `S` from `source` and runs both parts against it. Answers to the puzzle
+/// input are checked against the accepted ones.
+pub fn solve_input<S: Solution>(source: &input::InputSource) -> DayResult {
+    let mut result = match source.read(S::DAY) {
+        Ok(input) => run::<S>(&input),
+        Err(e) => DayResult::failed(Status::NoInput(e.to_string())),
+    };
+    if source.is_puzzle() {
+        answers::verify(S::DAY, &mut result.parts);
+    }
+    result
+}
+
+/// The body of a day's `main`: solves the day and prints the results as requested on the
+/// command line. The year of the day follows from the name of its binary, `bin_name`.
+///
+/// Exits with [`cli::Exit::Failure`] if a part failed or a benchmark regressed.
+pub fn solve_day<S: Solution>(bin_name: &str) {
+    let mut raw = pico_args::Arguments::from_env();
+    cli::handle_help(&mut raw, "solve");
+    let args = args::RunArgs::parse(raw).unwrap_or_else(|e| cli::usage_error("solve", e));
+    trace::init(&args.trace);
+
+    if args.year.is_some() {
+        cli::usage_error("solve", "--year picks the binary of the day, e.g. `cargo aoc solve 1 --year 2021` runs `cargo solve 2021-01`");
+    }
+    if let Some((Some(year), _)) = input::parse_bin_name(bin_name) {
+        input::set_year(year);
+    }
+
+    if args.limits.is_some() {
+        cli::usage_error("solve", "--timeout, --day-timeout and --memory-limit are only supported when running all days, e.g. `cargo aoc all --timeout 10`");
+    }
+
+    match args.bench {
+        Some(options) => match args.input.read(S::DAY) {
+            Ok(input) => {
+                let results = bench::bench::<S>(&input, &options);
+                bench::print_results(args.format, S::DAY, &results);
+                // Timings of other inputs are not comparable, so only the puzzle input has a history.
+                if args.input.is_puzzle() {
+                    record_bench::<S>(args.format, &results, args.compare.as_ref());
                 }
-                None => {
-                    println!("not solved.")
+            }
+            Err(e) => {
+                eprintln!("Could not read input: {e}");
+                suggest_input(&args.input);
+                cli::Exit::Environment.exit()
+            }
+        },
+        None => {
+            let result = solve_input::<S>(&args.input);
+            output::Printer::new(args.format).results(S::DAY, &result);
+            if args.accept {
+                accept::<S>(&result.parts);
+            }
+            match result.exit_code() {
+                cli::Exit::Success => {}
+                code @ cli::Exit::Environment => {
+                    suggest_input(&args.input);
+                    code.exit()
                 }
+                code => code.exit(),
             }
         }
+    }
+}
+
+/// Points out `--input` when the puzzle input of a single day could not be read. The all-days
+/// runner reads the same inputs, but has no such option.
+fn suggest_input(source: &input::InputSource) {
+    if source.is_puzzle() {
+        eprintln!("To solve another file, pass `--input <path>`.");
+    }
+}
+
+/// Records the answers of all solved parts as accepted.
+fn accept<S: Solution>(results: &[PartResult]) {
+    let mut accepted = answers::load(S::DAY)
+        .unwrap_or_else(|e| cli::fail(cli::Exit::Environment, format!("Could not read accepted answers: {e}")));
+    accepted.accept(results);
 
-        println!("🎄 {}Part {}{} 🎄", ANSI_BOLD, $part, ANSI_RESET);
-        print_result($solver, $input);
+    match answers::save(S::DAY, &accepted) {
+        Ok(path) => eprintln!("Recorded accepted answers in \"{}\".", path.display()),
+        Err(e) => cli::fail(cli::Exit::Environment, format!("Could not write accepted answers: {e}")),
+    }
+}
+
+/// Appends a benchmark to the history and, if asked to, compares it against an earlier run.
+/// Exits with a non-zero code if any part got slower than the threshold allows.
+fn record_bench<S: Solution>(format: output::Format, results: &[bench::BenchResult], compare: Option<&history::CompareOptions>) {
+    let current = history::entries(S::DAY, &history::current_commit(), results);
+
+    let previous = match history::load() {
+        Ok(previous) => previous,
+        Err(e) => {
+            eprintln!("Could not read benchmark history: {e}");
+            vec![]
+        }
+    };
+
+    if let Err(e) = history::append(&current) {
+        eprintln!("Could not write benchmark history: {e}");
+    }
+
+    if let Some(options) = compare {
+        let comparisons = history::compare(&previous, &current, options);
+        history::print_comparisons(format, &comparisons, options);
+        if comparisons.iter().any(|c| c.regressed) {
+            cli::Exit::Failure.exit();
+        }
+    }
+}
+
+#[macro_export]
+macro_rules! solve {
+    ($solution:ty) => {{
+        $crate::solve_day::<$solution>(env!("CARGO_BIN_NAME"));
     }};
 }
 
-pub fn read_file(folder: &str, day: u8) -> String {
-    let cwd = env::current_dir().unwrap();
+/// A type-erased [`Solution`], so that days with differing input and answer types can be
+/// stored side by side in a [`Registry`].
+#[derive(Clone, Copy)]
+pub struct Day {
+    pub day: u8,
+    /// The year of the day, or `None` for the default year.
+    year: Option<i32>,
+    runner: fn(&str, &mut Observer) -> DayResult,
+    solver: fn(&input::InputSource) -> DayResult,
+}
 
-    let filepath = cwd.join("src").join(folder).join(format!("{day:02}.txt"));
+impl Day {
+    pub fn new<S: Solution>() -> Self {
+        Self {
+            day: S::DAY,
+            year: None,
+            runner: run_observed::<S>,
+            solver: solve_input::<S>,
+        }
+    }
 
-    let f = fs::read_to_string(filepath);
-    f.expect("could not open input file")
+    /// Places the day in `year` instead of the default year.
+    pub fn in_year(mut self, year: i32) -> Self {
+        self.year = Some(year);
+        self
+    }
+
+    pub fn year(&self) -> i32 {
+        self.year.unwrap_or_else(input::default_year)
+    }
+
+    /// Runs both parts against `input`.
+    pub fn run(&self, input: &str) -> DayResult {
+        (self.runner)(input, &mut |_| {})
+    }
+
+    /// Runs both parts against `input`, reporting each phase as it finishes.
+    pub fn run_observed(&self, input: &str, observer: &mut Observer) -> DayResult {
+        (self.runner)(input, observer)
+    }
+
+    /// Reads the day's input from `source` and runs both parts against it.
+    pub fn solve(&self, source: &input::InputSource) -> DayResult {
+        (self.solver)(source)
+    }
 }
 
-fn parse_time(val: &str, postfix: &str) -> f64 {
-    val.split(postfix).next().unwrap().parse().unwrap()
+pub use layout::COMPILE_ERRORS_VAR;
+
+/// All solutions known to this repository, ordered by year and day. Days are looked up in the
+/// year that [`input::year`] is set to.
+///
+/// The registry for the solutions in `src/bin` is generated by `build.rs`. Binaries that need it
+/// can pull it in with `include!(concat!(env!("OUT_DIR"), "/days.rs"))`, which defines a
+/// `registry()` function.
+#[derive(Default)]
+pub struct Registry {
+    days: Vec<Day>,
+    compile_errors: Vec<(Option<i32>, u8, String)>,
 }
 
-pub fn parse_exec_time(output: &str) -> f64 {
-    output.lines().fold(0_f64, |acc, l| {
-        if !l.contains("elapsed:") {
-            acc
-        } else {
-            let timing = l.split("(elapsed: ").last().unwrap();
-            // use `contains` istd. of `ends_with`: string may contain ANSI escape sequences.
-            // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
-            if timing.contains("ns)") {
-                acc // range below rounding precision.
-            } else if timing.contains("µs)") {
-                acc + parse_time(timing, "µs") / 1000_f64
-            } else if timing.contains("ms)") {
-                acc + parse_time(timing, "ms")
-            } else if timing.contains("s)") {
-                acc + parse_time(timing, "s") * 1000_f64
-            } else {
-                acc
-            }
+impl Registry {
+    pub fn new(mut days: Vec<Day>) -> Self {
+        days.sort_by_key(|d| (d.year(), d.day));
+        Self {
+            days,
+            compile_errors: vec![],
+        }
+    }
+
+    /// Records days that have a source file in `src/bin` but could not be compiled in, by year
+    /// (`None` for the default year) and day.
+    pub fn with_compile_errors(mut self, compile_errors: Vec<(Option<i32>, u8, String)>) -> Self {
+        self.compile_errors = compile_errors;
+        self
+    }
+
+    /// Reads the input for `day` from `source` and runs both parts of its solution.
+    pub fn solve(&self, day: u8, source: &input::InputSource) -> DayResult {
+        match (self.unavailable(day), self.get(day)) {
+            (None, Some(solution)) => solution.solve(source),
+            (status, _) => DayResult::failed(status.unwrap_or(Status::Missing)),
+        }
+    }
+
+    /// Why `day` cannot be run, or `None` if it has a solution.
+    pub fn unavailable(&self, day: u8) -> Option<Status> {
+        let year = input::year();
+        let in_year = |y: &Option<i32>| y.unwrap_or_else(input::default_year) == year;
+        if let Some((_, _, error)) = self.compile_errors.iter().find(|(y, d, _)| in_year(y) && *d == day) {
+            return Some(Status::CompileError(error.clone()));
+        }
+        match self.get(day) {
+            Some(_) => None,
+            None => Some(Status::Missing),
         }
-    })
+    }
+
+    pub fn get(&self, day: u8) -> Option<&Day> {
+        let year = input::year();
+        self.days.iter().find(|d| d.year() == year && d.day == day)
+    }
+
+    /// All days of all years.
+    pub fn iter(&self) -> impl Iterator<Item = &Day> {
+        self.days.iter()
+    }
 }
 
-/// copied from: https://github.com/rust-lang/rust/blob/1.64.0/library/std/src/macros.rs#L328-L333
-#[cfg(test)]
-macro_rules! assert_approx_eq {
-    ($a:expr, $b:expr) => {{
-        let (a, b) = (&$a, &$b);
-        assert!(
-            (*a - *b).abs() < 1.0e-6,
-            "{} is not approximately equal to {}",
-            *a,
-            *b
-        );
-    }};
+/// Reads `src/<folder>/NN.txt`. See [`input::file_path`] for how the path is resolved.
+/// The front matter of example files is left out.
+pub fn try_read_file(folder: &str, day: u8) -> io::Result<String> {
+    let path = input::file_path(folder, day);
+    match folder {
+        "examples" => examples::Example::read(&path)
+            .map(|example| example.input().to_string())
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e)),
+        _ => input::read_path(&path),
+    }
+}
+
+pub fn read_file(folder: &str, day: u8) -> String {
+    try_read_file(folder, day).unwrap_or_else(|e| panic!("{e}"))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    struct Sum<const DAY: u8>;
+
+    impl<const DAY: u8> Solution for Sum<DAY> {
+        const DAY: u8 = DAY;
+
+        type Input = Vec<u32>;
+        type PartOne = u32;
+        type PartTwo = u32;
+
+        fn parse(input: &str) -> Result<Self::Input, ParseError> {
+            input.lines().map(parse::parse_field).collect()
+        }
+
+        fn part_one(input: &Self::Input) -> Option<u32> {
+            Some(input.iter().sum())
+        }
+
+        fn part_two(input: &Self::Input) -> Option<u32> {
+            assert!(!input.is_empty(), "no numbers");
+            None
+        }
+    }
+
     #[test]
-    fn test_parse_exec_time() {
-        assert_approx_eq!(
-            parse_exec_time(&format!(
-                "🎄 Part 1 🎄\n0 (elapsed: 74.13ns){ANSI_RESET}\n🎄 Part 2 🎄\n0 (elapsed: 50.00ns){ANSI_RESET}"
-            )),
-            0_f64
-        );
-
-        assert_approx_eq!(
-            parse_exec_time("🎄 Part 1 🎄\n0 (elapsed: 755µs)\n🎄 Part 2 🎄\n0 (elapsed: 700µs)"),
-            1.455_f64
-        );
-
-        assert_approx_eq!(
-            parse_exec_time("🎄 Part 1 🎄\n0 (elapsed: 70µs)\n🎄 Part 2 🎄\n0 (elapsed: 1.45ms)"),
-            1.52_f64
-        );
-
-        assert_approx_eq!(
-            parse_exec_time(
-                "🎄 Part 1 🎄\n0 (elapsed: 10.3s)\n🎄 Part 2 🎄\n0 (elapsed: 100.50ms)"
-            ),
-            10400.50_f64
-        );
+    fn test_registry() {
+        let registry = Registry::new(vec![
+            Day::new::<Sum<9>>(),
+            Day::new::<Sum<2>>(),
+            Day::new::<Sum<5>>().in_year(1999),
+        ]);
+
+        let days: Vec<(i32, u8)> = registry.iter().map(|d| (d.year(), d.day)).collect();
+        assert_eq!(days, vec![(1999, 5), (input::default_year(), 2), (input::default_year(), 9)]);
+        // Only days of the current year are looked up.
+        assert!(registry.get(5).is_none());
+
+        let [part_one, part_two] = registry.get(9).unwrap().run("1\n2\n3").parts;
+        assert_eq!(part_one.answer, Some("6".to_string()));
+        assert_eq!(part_two.answer, None);
+        assert_eq!(part_two.status, Status::Unsolved);
+    }
+
+    #[test]
+    fn test_run_catches_panics() {
+        let registry = Registry::new(vec![Day::new::<Sum<1>>()])
+            .with_compile_errors(vec![(None, 2, "expected `;`".to_string()), (Some(1999), 3, "expected `}`".to_string())]);
+
+        let [_, part_two] = registry.get(1).unwrap().run("").parts;
+        assert_eq!(part_two.status, Status::Panicked("no numbers".to_string()));
+
+        let [part_one, part_two] = registry.get(1).unwrap().run("1\nx").parts;
+        assert_eq!(part_one.status.name(), "invalid_input");
+        assert_eq!(part_two.status.message(), Some("line 2, column 1: expected u32: invalid digit found in string".to_string()));
+
+        let [part_one, _] = registry.solve(2, &input::InputSource::Puzzle).parts;
+        assert_eq!(part_one.status, Status::CompileError("expected `;`".to_string()));
+
+        let [part_one, _] = registry.solve(3, &input::InputSource::Puzzle).parts;
+        assert_eq!(part_one.status, Status::Missing);
+    }
+
+    #[test]
+    fn test_exit_code() {
+        let day = Day::new::<Sum<1>>();
+        assert_eq!(day.run("1\n2").exit_code(), cli::Exit::Success);
+        assert_eq!(day.run("").exit_code(), cli::Exit::Failure);
+
+        let missing = input::InputSource::File("does/not/exist.txt".into());
+        let result = solve_input::<Sum<1>>(&missing);
+        assert!(matches!(result.parts[0].status, Status::NoInput(_)));
+        assert_eq!(result.exit_code(), cli::Exit::Environment);
     }
 }
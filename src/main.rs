@@ -2,42 +2,82 @@
  * This file contains template code.
  * There is no need to edit this file unless you want to change template functionality.
  */
-use advent_of_code::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
-use std::process::Command;
+// Solutions are compiled into this binary, so any features they use need to be enabled here too.
+#![cfg_attr(not(test), feature(int_roundings))]
 
+// Every solution in `src/bin` is compiled into this binary as well. Their unit tests already run
+// as part of each day's own binary, so they are left out of this one's test build.
+#[cfg(not(test))]
+mod days {
+    include!(concat!(env!("OUT_DIR"), "/days.rs"));
+}
+
+// `--memory-limit` is enforced by the counting allocator, which only counts once a worker sets a
+// limit. The `memory` feature already installs it.
+#[cfg(all(not(test), not(feature = "memory")))]
+#[global_allocator]
+static ALLOCATOR: advent_of_code::memory::CountingAllocator =
+    advent_of_code::memory::CountingAllocator;
+
+#[cfg(not(test))]
 fn main() {
-    let total: f64 = (1..=25)
+    use advent_of_code::cli::{self, Exit};
+    use advent_of_code::{args::RunArgs, input::InputSource, output::Printer, worker};
+    use std::env;
+    use std::time::Duration;
+
+    let registry = days::registry();
+
+    // Each day is solved in a worker, a copy of this binary, so that it can be stopped.
+    if env::args().nth(1).as_deref() == Some(worker::WORKER_ARG) {
+        worker::serve(&registry);
+    }
+
+    let mut raw = pico_args::Arguments::from_env();
+    cli::handle_help(&mut raw, "all");
+    let args = RunArgs::parse(raw).unwrap_or_else(|e| cli::usage_error("all", e));
+
+    if args.bench.is_some() {
+        cli::usage_error(
+            "all",
+            "--bench is only supported for a single day, e.g. `cargo aoc bench 1`",
+        );
+    }
+
+    if args.accept {
+        cli::usage_error(
+            "all",
+            "--accept is only supported for a single day, e.g. `cargo aoc solve 1 --accept`",
+        );
+    }
+
+    if matches!(args.input, InputSource::File(_) | InputSource::Stdin) {
+        cli::usage_error("all", "--input and - are only supported for a single day, e.g. `cargo aoc solve 1 --input <path>`");
+    }
+
+    advent_of_code::trace::init(&args.trace);
+    if let Some(year) = args.year {
+        advent_of_code::input::set_year(year);
+    }
+    let limits = args
+        .limits
+        .unwrap_or_else(worker::Limits::configured);
+    let mut printer = Printer::new(args.format);
+
+    let mut failed = false;
+    let total: Duration = (1..=25)
         .map(|day| {
-            let day = format!("{day:02}");
-
-            let cmd = Command::new("cargo")
-                .args(["run", "--release", "--bin", &day])
-                .output()
-                .unwrap();
-
-            println!("----------");
-            println!("{ANSI_BOLD}| Day {day} |{ANSI_RESET}");
-            println!("----------");
-
-            let output = String::from_utf8(cmd.stdout).unwrap();
-            let is_empty = output.is_empty();
-
-            println!(
-                "{}",
-                if is_empty {
-                    "Not solved."
-                } else {
-                    output.trim()
-                }
-            );
-
-            if is_empty {
-                0_f64
-            } else {
-                advent_of_code::parse_exec_time(&output)
-            }
+            printer.day_header(day);
+
+            let result = worker::solve(&registry, day, &args.input, &limits);
+            printer.results(day, &result);
+            failed |= result.has_failures();
+            result.elapsed()
         })
         .sum();
 
-    println!("{ANSI_BOLD}Total:{ANSI_RESET} {ANSI_ITALIC}{total:.2}ms{ANSI_RESET}");
+    printer.total(total);
+    if failed {
+        Exit::Failure.exit();
+    }
 }
@@ -0,0 +1,202 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+/// Heap usage of one part, as counted by [`CountingAllocator`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MemoryStats {
+    /// The most heap memory held at once, on top of what was held before the part started.
+    pub peak_bytes: usize,
+    /// All bytes requested from the allocator, including memory that was freed again.
+    pub allocated_bytes: usize,
+    /// The number of allocations. A `realloc` counts as one.
+    pub allocations: usize,
+}
+
+static CURRENT: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+static ALLOCATED: AtomicUsize = AtomicUsize::new(0);
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+static LIMIT: AtomicUsize = AtomicUsize::new(0);
+/// Whether allocations are counted, see [`CountingAllocator`].
+static COUNTING: AtomicBool = AtomicBool::new(cfg!(feature = "memory"));
+
+/// The exit code of a process that exceeded the limit set by [`set_limit`].
+pub const LIMIT_EXIT_CODE: i32 = 75;
+
+/// Wraps the system allocator and counts every allocation.
+///
+/// It is installed as the global allocator when the `memory` feature is enabled, e.g.
+/// `cargo solve 11 --features memory`. The all-days runner always installs it to enforce
+/// `--memory-limit`, but it only starts counting once [`set_limit`] is called. Until then, each
+/// allocation costs a single relaxed load on top of the system allocator.
+pub struct CountingAllocator;
+
+/// Ends the process if growing the heap by `bytes` would exceed the limit.
+fn check_limit(bytes: usize) {
+    let limit = LIMIT.load(Ordering::Relaxed);
+    if limit != 0 && CURRENT.load(Ordering::Relaxed) + bytes > limit {
+        // This runs inside the allocator, so nothing may allocate: no unwinding, no reporting and
+        // no exit handlers. All the runner gets is the exit code.
+        unsafe { libc::_exit(LIMIT_EXIT_CODE) }
+    }
+}
+
+/// Subtracts `bytes` from the heap in use. Memory allocated before counting started is freed
+/// without ever having been added, so this stops at zero.
+fn release(bytes: usize) {
+    let _ = CURRENT.fetch_update(Ordering::Relaxed, Ordering::Relaxed, |current| {
+        Some(current.saturating_sub(bytes))
+    });
+}
+
+fn record(freed: usize, allocated: usize) {
+    ALLOCATED.fetch_add(allocated, Ordering::Relaxed);
+    ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+    release(freed);
+    let current = CURRENT.fetch_add(allocated, Ordering::Relaxed) + allocated;
+    PEAK.fetch_max(current, Ordering::Relaxed);
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        if !COUNTING.load(Ordering::Relaxed) {
+            return System.alloc(layout);
+        }
+        check_limit(layout.size());
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            record(0, layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        if !COUNTING.load(Ordering::Relaxed) {
+            return System.alloc_zeroed(layout);
+        }
+        check_limit(layout.size());
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            record(0, layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        if !COUNTING.load(Ordering::Relaxed) {
+            return System.realloc(ptr, layout, new_size);
+        }
+        check_limit(new_size.saturating_sub(layout.size()));
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            record(layout.size(), new_size);
+        }
+        new_ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        if COUNTING.load(Ordering::Relaxed) {
+            release(layout.size());
+        }
+    }
+}
+
+#[cfg(feature = "memory")]
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+// The worker tests enforce a memory limit in this test binary.
+#[cfg(all(test, not(feature = "memory")))]
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+/// Whether allocations are counted, i.e. whether the `memory` feature is enabled.
+pub fn enabled() -> bool {
+    cfg!(feature = "memory")
+}
+
+/// Ends the process with [`LIMIT_EXIT_CODE`] once the heap would grow beyond `bytes`, and starts
+/// counting allocations if they are not counted yet. Only takes effect if [`CountingAllocator`]
+/// is the global allocator.
+pub fn set_limit(bytes: usize) {
+    LIMIT.store(bytes, Ordering::Relaxed);
+    COUNTING.store(true, Ordering::Relaxed);
+}
+
+/// Calls `f` and returns its heap usage, or `None` if allocations are not counted.
+///
+/// Allocations made by other threads while `f` runs are counted as well, so parts should be
+/// measured one at a time.
+pub fn measure<T>(f: impl FnOnce() -> T) -> (T, Option<MemoryStats>) {
+    if !enabled() {
+        return (f(), None);
+    }
+
+    let baseline = CURRENT.load(Ordering::Relaxed);
+    PEAK.store(baseline, Ordering::Relaxed);
+    let allocated = ALLOCATED.load(Ordering::Relaxed);
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed);
+
+    let value = f();
+
+    let stats = MemoryStats {
+        peak_bytes: PEAK
+            .load(Ordering::Relaxed)
+            .saturating_sub(baseline),
+        allocated_bytes: ALLOCATED.load(Ordering::Relaxed) - allocated,
+        allocations: ALLOCATIONS.load(Ordering::Relaxed) - allocations,
+    };
+    (value, Some(stats))
+}
+
+/// Formats a number of bytes with a binary unit, e.g. `1.50 KiB`.
+pub fn format_bytes(bytes: usize) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    format!("{value:.2} {}", UNITS[unit])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_bytes() {
+        assert_eq!(format_bytes(512), "512 B");
+        assert_eq!(format_bytes(1536), "1.50 KiB");
+        assert_eq!(format_bytes(3 * 1024 * 1024), "3.00 MiB");
+    }
+
+    #[test]
+    fn test_measure() {
+        let (sum, stats) = measure(|| {
+            (0..1000u64)
+                .collect::<Vec<_>>()
+                .iter()
+                .sum::<u64>()
+        });
+        assert_eq!(sum, 499500);
+        match stats {
+            Some(stats) => {
+                assert!(stats.allocations >= 1);
+                assert!(stats.allocated_bytes >= 8000);
+                assert!(stats.peak_bytes >= 8000);
+            }
+            None => assert!(!enabled()),
+        }
+    }
+}
@@ -0,0 +1,295 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use crate::answers::Verdict;
+use crate::memory::{self, MemoryStats};
+use crate::{DayResult, PartResult, Status, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+use std::str::FromStr;
+use std::time::Duration;
+
+/// How results are written to stdout.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Format {
+    /// Human-readable output.
+    #[default]
+    Text,
+    /// One JSON object per part and line.
+    Json,
+    /// Comma-separated values with a header row.
+    Csv,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            "csv" => Ok(Format::Csv),
+            _ => Err(format!(
+                "unknown format \"{s}\", expected one of: text, json, csv"
+            )),
+        }
+    }
+}
+
+const CSV_HEADER: &str = "day,part,answer,duration_ns,status,message,verdict,expected,parse_ns,peak_bytes,allocated_bytes,allocations";
+
+fn json_string(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len() + 2);
+    escaped.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if c.is_control() => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped.push('"');
+    escaped
+}
+
+pub(crate) fn json_value(value: Option<&str>) -> String {
+    value
+        .map(json_string)
+        .unwrap_or_else(|| "null".to_string())
+}
+
+/// The memory columns of a record, `None` if allocations were not counted.
+fn memory_fields(stats: Option<MemoryStats>) -> [Option<String>; 3] {
+    match stats {
+        Some(stats) => [stats.peak_bytes, stats.allocated_bytes, stats.allocations]
+            .map(|n| Some(n.to_string())),
+        None => [None, None, None],
+    }
+}
+
+pub(crate) fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Renders a part's result as a single-line JSON object. `parse_elapsed` is the day's parse time.
+pub fn to_json(day: u8, result: &PartResult, parse_elapsed: Duration) -> String {
+    let [peak, allocated, allocations] =
+        memory_fields(result.memory).map(|n| n.unwrap_or_else(|| "null".to_string()));
+    format!(
+        "{{\"day\":{day},\"part\":{},\"answer\":{},\"duration_ns\":{},\"status\":\"{}\",\"message\":{},\"verdict\":{},\"expected\":{},\"parse_ns\":{},\"peak_bytes\":{peak},\"allocated_bytes\":{allocated},\"allocations\":{allocations}}}",
+        result.part,
+        json_value(result.answer.as_deref()),
+        result.elapsed.as_nanos(),
+        result.status.name(),
+        json_value(result.status.message().as_deref()),
+        json_value(result.verdict.as_ref().map(Verdict::name)),
+        json_value(result.verdict.as_ref().and_then(Verdict::expected)),
+        parse_elapsed.as_nanos(),
+    )
+}
+
+/// Renders a part's result as a CSV row matching [`CSV_HEADER`].
+pub fn to_csv(day: u8, result: &PartResult, parse_elapsed: Duration) -> String {
+    let [peak, allocated, allocations] =
+        memory_fields(result.memory).map(Option::unwrap_or_default);
+    format!(
+        "{day},{},{},{},{},{},{},{},{},{peak},{allocated},{allocations}",
+        result.part,
+        csv_field(
+            result
+                .answer
+                .as_deref()
+                .unwrap_or_default()
+        ),
+        result.elapsed.as_nanos(),
+        result.status.name(),
+        csv_field(
+            &result
+                .status
+                .message()
+                .unwrap_or_default()
+        ),
+        result
+            .verdict
+            .as_ref()
+            .map(Verdict::name)
+            .unwrap_or_default(),
+        csv_field(
+            result
+                .verdict
+                .as_ref()
+                .and_then(Verdict::expected)
+                .unwrap_or_default()
+        ),
+        parse_elapsed.as_nanos(),
+    )
+}
+
+pub fn print_result(result: &PartResult) {
+    println!("🎄 {ANSI_BOLD}Part {}{ANSI_RESET} 🎄", result.part);
+    match (&result.status, &result.answer) {
+        (Status::Solved, Some(answer)) => {
+            let elapsed = result.elapsed;
+            match result.memory {
+                Some(stats) => println!(
+                    "{answer} {ANSI_ITALIC}(elapsed: {elapsed:.2?}, peak: {}, allocated: {} in {} allocations){ANSI_RESET}",
+                    memory::format_bytes(stats.peak_bytes),
+                    memory::format_bytes(stats.allocated_bytes),
+                    stats.allocations
+                ),
+                None => println!("{answer} {ANSI_ITALIC}(elapsed: {elapsed:.2?}){ANSI_RESET}"),
+            }
+            match &result.verdict {
+                Some(Verdict::Correct) => println!("✔ correct"),
+                Some(Verdict::Wrong(expected)) => {
+                    println!("✘ {ANSI_BOLD}wrong{ANSI_RESET}, expected: {expected}")
+                }
+                Some(Verdict::Unknown) => println!("? unknown, no accepted answer yet"),
+                None => {}
+            }
+        }
+        (Status::Panicked(message), _) => {
+            println!("panicked: {message}")
+        }
+        (status @ (Status::TimedOut(_) | Status::OutOfMemory(_)), _) => {
+            println!(
+                "stopped: {}",
+                status
+                    .message()
+                    .unwrap_or_default()
+            )
+        }
+        (_, _) if matches!(result.verdict, Some(Verdict::Wrong(_))) => {
+            let expected = result
+                .verdict
+                .as_ref()
+                .and_then(Verdict::expected)
+                .unwrap_or_default();
+            println!("not solved, expected: {expected}")
+        }
+        _ => {
+            println!("not solved.")
+        }
+    }
+}
+
+/// Writes results to stdout in the requested [`Format`].
+pub struct Printer {
+    format: Format,
+    printed_header: bool,
+}
+
+impl Printer {
+    pub fn new(format: Format) -> Self {
+        Self {
+            format,
+            printed_header: false,
+        }
+    }
+
+    /// Prints the banner that separates days when running all of them.
+    pub fn day_header(&self, day: u8) {
+        if self.format == Format::Text {
+            println!("----------");
+            println!("{ANSI_BOLD}| Day {day:02} |{ANSI_RESET}");
+            println!("----------");
+        }
+    }
+
+    pub fn results(&mut self, day: u8, result: &DayResult) {
+        let (results, parse_elapsed) = (&result.parts, result.parse_elapsed);
+        match self.format {
+            Format::Text => match &results[0].status {
+                Status::Missing => println!("Not solved."),
+                Status::CompileError(error) => println!("Failed to compile: {error}"),
+                Status::Invalid(error) => println!("{error}"),
+                Status::NoInput(error) => println!("Could not read input: {error}"),
+                // A day stopped before parsing finished has no parse time to show.
+                status @ (Status::TimedOut(_) | Status::OutOfMemory(_))
+                    if parse_elapsed.is_zero() =>
+                {
+                    println!(
+                        "Stopped while parsing: {}",
+                        status
+                            .message()
+                            .unwrap_or_default()
+                    )
+                }
+                _ => {
+                    println!("🎄 {ANSI_BOLD}Parse{ANSI_RESET} 🎄");
+                    println!("{ANSI_ITALIC}(elapsed: {parse_elapsed:.2?}){ANSI_RESET}");
+                    results
+                        .iter()
+                        .for_each(print_result);
+                }
+            },
+            Format::Json => results
+                .iter()
+                .for_each(|result| println!("{}", to_json(day, result, parse_elapsed))),
+            Format::Csv => {
+                if !self.printed_header {
+                    println!("{CSV_HEADER}");
+                    self.printed_header = true;
+                }
+                results
+                    .iter()
+                    .for_each(|result| println!("{}", to_csv(day, result, parse_elapsed)));
+            }
+        }
+    }
+
+    /// Prints the summed duration of parsing and all solved parts. Structured formats leave this to
+    /// the reader.
+    pub fn total(&self, total: Duration) {
+        if self.format == Format::Text {
+            let total = total.as_secs_f64() * 1000_f64;
+            println!("{ANSI_BOLD}Total:{ANSI_RESET} {ANSI_ITALIC}{total:.2}ms{ANSI_RESET}");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_structured_records() {
+        let solved = PartResult {
+            part: 2,
+            status: Status::Solved,
+            answer: Some("##..\n\"a\", b".to_string()),
+            elapsed: Duration::from_nanos(1_250),
+            memory: Some(MemoryStats {
+                peak_bytes: 4096,
+                allocated_bytes: 6144,
+                allocations: 3,
+            }),
+            verdict: Some(Verdict::Wrong("42".to_string())),
+        };
+        assert_eq!(
+            to_json(10, &solved, Duration::from_nanos(500)),
+            r###"{"day":10,"part":2,"answer":"##..\n\"a\", b","duration_ns":1250,"status":"solved","message":null,"verdict":"wrong","expected":"42","parse_ns":500,"peak_bytes":4096,"allocated_bytes":6144,"allocations":3}"###
+        );
+        assert_eq!(
+            to_csv(10, &solved, Duration::from_nanos(500)),
+            "10,2,\"##..\n\"\"a\"\", b\",1250,solved,,wrong,42,500,4096,6144,3"
+        );
+
+        let panicked = PartResult::failed(1, Status::Panicked("oops".to_string()));
+        assert_eq!(
+            to_json(3, &panicked, Duration::ZERO),
+            r###"{"day":3,"part":1,"answer":null,"duration_ns":0,"status":"panicked","message":"oops","verdict":null,"expected":null,"parse_ns":0,"peak_bytes":null,"allocated_bytes":null,"allocations":null}"###
+        );
+        assert_eq!(
+            to_csv(3, &panicked, Duration::ZERO),
+            "3,1,,0,panicked,oops,,,0,,,"
+        );
+    }
+}
@@ -0,0 +1,175 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use std::any::type_name;
+use std::fmt::{self, Display};
+use std::str::FromStr;
+
+/// An error in the puzzle input, pointing at the text that could not be parsed.
+///
+/// Solutions create it from a slice of their input. Once [`Solution::parse`](crate::Solution::parse)
+/// returns, the runner locates that slice in the full input, so `line`, `column` and `source_line`
+/// are filled in without solutions having to track positions themselves.
+#[derive(Debug, Clone, Eq)]
+pub struct ParseError {
+    pub message: String,
+    /// The offending text.
+    pub text: String,
+    /// 1-based line of the offending text, or `0` if it has not been located.
+    pub line: usize,
+    /// 1-based column of the offending text, counted in characters.
+    pub column: usize,
+    /// The full line containing the offending text.
+    pub source_line: String,
+    address: usize,
+}
+
+impl ParseError {
+    /// Creates an error for `text`, which should be a slice of the puzzle input.
+    pub fn new(text: &str, message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+            text: text.to_string(),
+            line: 0,
+            column: 0,
+            source_line: String::new(),
+            address: text.as_ptr() as usize,
+        }
+    }
+
+    /// Fills in the position of the offending text if it is a slice of `input`.
+    pub fn locate(mut self, input: &str) -> Self {
+        let start = input.as_ptr() as usize;
+        if self.line != 0 || self.address < start || self.address > start + input.len() {
+            return self;
+        }
+
+        let offset = self.address - start;
+        let before = &input[..offset];
+        let line_start = before
+            .rfind('\n')
+            .map_or(0, |i| i + 1);
+        let line_end = input[offset..]
+            .find('\n')
+            .map_or(input.len(), |i| offset + i);
+
+        self.line = before.matches('\n').count() + 1;
+        self.column = before[line_start..]
+            .chars()
+            .count()
+            + 1;
+        self.source_line = input[line_start..line_end]
+            .trim_end_matches('\r')
+            .to_string();
+        self
+    }
+
+    /// A single-line description, e.g. for machine-readable output.
+    pub fn summary(&self) -> String {
+        match self.line {
+            0 => format!("{}: {:?}", self.message, self.text),
+            line => format!("line {line}, column {}: {}", self.column, self.message),
+        }
+    }
+}
+
+/// Errors are equal if they report the same problem at the same position. Where the offending
+/// text was stored in memory does not matter.
+impl PartialEq for ParseError {
+    fn eq(&self, other: &Self) -> bool {
+        (
+            &self.message,
+            &self.text,
+            self.line,
+            self.column,
+            &self.source_line,
+        ) == (
+            &other.message,
+            &other.text,
+            other.line,
+            other.column,
+            &other.source_line,
+        )
+    }
+}
+
+/// Renders the error as a diagnostic with the offending text underlined.
+impl Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.line == 0 {
+            return write!(f, "error: {}: {:?}", self.message, self.text);
+        }
+
+        let gutter = " ".repeat(self.line.to_string().len());
+        let underline = self
+            .text
+            .lines()
+            .next()
+            .map_or(0, |text| text.chars().count())
+            .max(1);
+
+        writeln!(f, "error: {}", self.message)?;
+        writeln!(f, "{gutter}--> line {}, column {}", self.line, self.column)?;
+        writeln!(f, "{gutter} |")?;
+        writeln!(f, "{} | {}", self.line, self.source_line)?;
+        write!(
+            f,
+            "{gutter} | {}{}",
+            " ".repeat(self.column - 1),
+            "^".repeat(underline)
+        )
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Parses `text` into `T`, reporting failures as a [`ParseError`] for `text`.
+pub fn parse_field<T>(text: &str) -> Result<T, ParseError>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    text.parse().map_err(|e| {
+        let name = type_name::<T>()
+            .rsplit("::")
+            .next()
+            .unwrap_or_default();
+        ParseError::new(text, format!("expected {name}: {e}"))
+    })
+}
+
+/// Strips `prefix` from `text`, or reports that it was expected.
+pub fn expect_prefix<'a>(text: &'a str, prefix: &str) -> Result<&'a str, ParseError> {
+    text.strip_prefix(prefix)
+        .ok_or_else(|| ParseError::new(text, format!("expected {:?}", prefix.trim())))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_locate() {
+        let input = "R 4\nU x\n";
+        let line = input.lines().nth(1).unwrap();
+        let error = parse_field::<u32>(&line[2..])
+            .unwrap_err()
+            .locate(input);
+
+        assert_eq!((error.line, error.column), (2, 3));
+        assert_eq!(error.source_line, "U x");
+        assert_eq!(
+            error.summary(),
+            "line 2, column 3: expected u32: invalid digit found in string"
+        );
+        assert_eq!(
+            error.to_string(),
+            "error: expected u32: invalid digit found in string\n --> line 2, column 3\n  |\n2 | U x\n  |   ^"
+        );
+
+        let elsewhere = ParseError::new("x", "unexpected").locate(input);
+        assert_eq!(elsewhere.line, 0);
+        assert_eq!(elsewhere.summary(), "unexpected: \"x\"");
+    }
+}
@@ -0,0 +1,465 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use crate::answers::Answers;
+use crate::examples::Example;
+use crate::input;
+use std::path::PathBuf;
+
+/// Where the description of `day` is stored, e.g. `src/puzzles/01.md`.
+pub fn file_path(day: u8) -> PathBuf {
+    input::folder_dir("puzzles").join(format!("{day:02}.md"))
+}
+
+/// The `<article class="day-desc">` elements of a puzzle page, one per unlocked part.
+fn articles(page: &str) -> Vec<&str> {
+    let mut articles = vec![];
+    let mut rest = page;
+    while let Some(start) = rest.find("<article class=\"day-desc\">") {
+        let article = &rest[start..];
+        let end = article
+            .find("</article>")
+            .unwrap_or(article.len());
+        articles.push(&article[..end]);
+        rest = &article[end..];
+    }
+    articles
+}
+
+/// Code blocks shorter than this are rather snippets than an example input.
+const MIN_EXAMPLE_LEN: usize = 10;
+
+/// Extracts the example input from a puzzle page: the first code block that spans several lines
+/// or is at least [`MIN_EXAMPLE_LEN`] long, or else the longest one.
+///
+/// The expected answers are guessed from the last emphasized code in the description of each part,
+/// which is where the puzzles usually state the answer to the example. They may need correcting.
+pub fn example(page: &str) -> Option<Example> {
+    let mut blocks = vec![];
+    let mut answers = [None, None];
+    for (article, answer) in articles(page)
+        .into_iter()
+        .zip(&mut answers)
+    {
+        let mut pre: Option<String> = None;
+        let mut code: Option<String> = None;
+        let mut emphasis = 0;
+        let mut emphasized = false;
+        for token in tokenize(article) {
+            match token {
+                Token::Open("pre", _) => pre = Some(String::new()),
+                Token::Close("pre") => blocks.extend(pre.take()),
+                Token::Text(text) => match (&mut pre, &mut code) {
+                    (Some(pre), _) => pre.push_str(&decode_entities(text)),
+                    (None, Some(code)) => code.push_str(&decode_entities(text)),
+                    _ => {}
+                },
+                _ if pre.is_some() => {}
+                Token::Open("code", _) => {
+                    code = Some(String::new());
+                    emphasized = emphasis > 0;
+                }
+                Token::Close("code") => {
+                    let code = code
+                        .take()
+                        .unwrap_or_default();
+                    if emphasized && !code.trim().is_empty() {
+                        *answer = Some(code.trim().to_string());
+                    }
+                }
+                Token::Open("em", _) => {
+                    emphasis += 1;
+                    emphasized |= code.is_some();
+                }
+                Token::Close("em") => emphasis -= 1,
+                _ => {}
+            }
+        }
+    }
+
+    let is_large = |block: &&String| {
+        block
+            .trim_end()
+            .contains('\n')
+            || block.len() >= MIN_EXAMPLE_LEN
+    };
+    let input = blocks
+        .iter()
+        .find(is_large)
+        .or_else(|| {
+            blocks
+                .iter()
+                .max_by_key(|block| block.len())
+        })?;
+    let [part_one, part_two] = answers;
+    Some(Example::new(Answers { part_one, part_two }, input))
+}
+
+/// Converts the puzzle description on a puzzle page to Markdown.
+///
+/// Only the `<article class="day-desc">` elements are converted, so part two is included once it
+/// is unlocked. Returns `None` if the page has no description.
+pub fn to_markdown(page: &str) -> Option<String> {
+    let articles = articles(page);
+    let mut renderer = Renderer::default();
+    for article in &articles {
+        for token in tokenize(article) {
+            renderer.render(token);
+        }
+        renderer.flush();
+    }
+    (!articles.is_empty()).then(|| renderer.blocks.join("\n\n") + "\n")
+}
+
+/// The title of a puzzle from its Markdown description, e.g. `Calorie Counting`.
+pub fn title(markdown: &str) -> Option<String> {
+    let heading = markdown
+        .lines()
+        .find_map(|line| line.strip_prefix("## "))?;
+    let (_, title) = heading
+        .trim_matches(|c: char| c == '-' || c.is_whitespace())
+        .split_once(": ")?;
+    Some(title.to_string())
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum Token<'a> {
+    /// A start tag with its name and attributes.
+    Open(&'a str, &'a str),
+    Close(&'a str),
+    Text(&'a str),
+}
+
+fn tokenize(html: &str) -> Vec<Token<'_>> {
+    let mut tokens = vec![];
+    let mut rest = html;
+    while !rest.is_empty() {
+        if let Some(comment) = rest.strip_prefix("<!--") {
+            rest = comment
+                .find("-->")
+                .map_or("", |end| &comment[end + 3..]);
+        } else if let Some(tag) = rest.strip_prefix('<') {
+            let end = tag
+                .find('>')
+                .unwrap_or(tag.len());
+            let inner = tag[..end].trim_end_matches('/');
+            match inner.strip_prefix('/') {
+                Some(name) => tokens.push(Token::Close(name.trim())),
+                None => {
+                    let (name, attrs) = inner
+                        .split_once(char::is_whitespace)
+                        .unwrap_or((inner, ""));
+                    tokens.push(Token::Open(name, attrs));
+                }
+            }
+            rest = tag
+                .get(end + 1..)
+                .unwrap_or("");
+        } else {
+            let end = rest
+                .find('<')
+                .unwrap_or(rest.len());
+            tokens.push(Token::Text(&rest[..end]));
+            rest = &rest[end..];
+        }
+    }
+    tokens
+}
+
+/// The value of attribute `name` in the attributes of a start tag.
+fn attribute<'a>(attrs: &'a str, name: &str) -> Option<&'a str> {
+    let value = &attrs[attrs.find(&format!("{name}=\""))? + name.len() + 2..];
+    value.split('"').next()
+}
+
+/// Replaces the character references in `text` by the characters they stand for.
+fn decode_entities(text: &str) -> String {
+    let mut decoded = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('&') {
+        decoded.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let entity = rest
+            .find(';')
+            .map(|end| (&rest[1..end], end));
+        let c = entity.and_then(|(name, _)| match name {
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "amp" => Some('&'),
+            "quot" => Some('"'),
+            "apos" => Some('\''),
+            "nbsp" => Some(' '),
+            _ => match name.strip_prefix("#x") {
+                Some(hex) => u32::from_str_radix(hex, 16).ok(),
+                None => name
+                    .strip_prefix('#')
+                    .and_then(|dec| dec.parse().ok()),
+            }
+            .and_then(char::from_u32),
+        });
+        match (c, entity) {
+            (Some(c), Some((_, end))) => {
+                decoded.push(c);
+                rest = &rest[end + 1..];
+            }
+            _ => {
+                decoded.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    decoded.push_str(rest);
+    decoded
+}
+
+/// Escapes the characters that Markdown would otherwise read as formatting.
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if matches!(c, '\\' | '*' | '_' | '`') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// Wraps `code` in enough backticks that none inside it end the code span.
+fn code_span(code: &str) -> String {
+    let mut fence = String::from("`");
+    while code.contains(&fence) {
+        fence.push('`');
+    }
+    let padding = if code.starts_with('`') || code.ends_with('`') {
+        " "
+    } else {
+        ""
+    };
+    format!("{fence}{padding}{code}{padding}{fence}")
+}
+
+#[derive(Default)]
+struct Renderer {
+    blocks: Vec<String>,
+    /// The paragraph, heading or list item being written.
+    line: String,
+    /// The items of the list being written, with nested lists indented.
+    list: Vec<String>,
+    list_depth: usize,
+    /// The contents of the `<pre>` block being written.
+    pre: Option<String>,
+    /// The contents of the inline `<code>` being written, and whether any of it is emphasized.
+    code: Option<(String, bool)>,
+    links: Vec<Option<String>>,
+}
+
+impl Renderer {
+    fn render(&mut self, token: Token) {
+        match token {
+            Token::Text(text) => self.text(&decode_entities(text)),
+            Token::Open("pre", _) => {
+                self.flush();
+                self.pre = Some(String::new());
+            }
+            Token::Close("pre") => {
+                if let Some(pre) = self.pre.take() {
+                    self.blocks
+                        .push(format!("```\n{}\n```", pre.trim_end_matches('\n')));
+                }
+            }
+            // Tags inside code blocks, e.g. emphasis, cannot be shown in Markdown.
+            _ if self.pre.is_some() => {}
+            Token::Open("h2", _) => {
+                self.flush();
+                self.line.push_str("## ");
+            }
+            Token::Open("p", _) | Token::Close("p" | "h2") => self.flush(),
+            Token::Open("ul" | "ol", _) => {
+                self.flush();
+                self.list_depth += 1;
+            }
+            Token::Close("ul" | "ol") => {
+                self.flush();
+                self.list_depth = self
+                    .list_depth
+                    .saturating_sub(1);
+                if self.list_depth == 0 && !self.list.is_empty() {
+                    let list = self
+                        .list
+                        .drain(..)
+                        .collect::<Vec<_>>();
+                    self.blocks
+                        .push(list.join("\n"));
+                }
+            }
+            Token::Open("li", _) => {
+                self.flush();
+                self.line = format!(
+                    "{}- ",
+                    "  ".repeat(
+                        self.list_depth
+                            .saturating_sub(1)
+                    )
+                );
+            }
+            Token::Close("li") => self.flush(),
+            Token::Open("code", _) => self.code = Some((String::new(), false)),
+            Token::Close("code") => {
+                if let Some((code, emphasized)) = self.code.take() {
+                    let span = code_span(code.trim());
+                    match emphasized {
+                        true => self.push(&format!("*{span}*")),
+                        false => self.push(&span),
+                    }
+                }
+            }
+            Token::Open("em", _) | Token::Close("em") => match &mut self.code {
+                Some((_, emphasized)) => *emphasized = true,
+                None => self.push("*"),
+            },
+            Token::Open("a", attrs) => {
+                let href = attribute(attrs, "href").map(decode_entities);
+                if href.is_some() {
+                    self.push("[");
+                }
+                self.links.push(href);
+            }
+            Token::Close("a") => {
+                if let Some(Some(href)) = self.links.pop() {
+                    self.push(&format!("]({href})"));
+                }
+            }
+            Token::Open("br", _) => self.text(" "),
+            _ => {}
+        }
+    }
+
+    fn text(&mut self, text: &str) {
+        if let Some(pre) = &mut self.pre {
+            pre.push_str(text);
+            return;
+        }
+        let collapsed = text
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ");
+        let leading = text.starts_with(char::is_whitespace) && !collapsed.is_empty();
+        let trailing = text.ends_with(char::is_whitespace);
+        let mut inline = String::new();
+        if leading || (collapsed.is_empty() && trailing) {
+            inline.push(' ');
+        }
+        match &mut self.code {
+            Some((code, _)) => {
+                code.push_str(&inline);
+                code.push_str(&collapsed);
+            }
+            None => {
+                inline.push_str(&escape(&collapsed));
+                self.push(&inline);
+            }
+        }
+        if trailing && !collapsed.is_empty() {
+            self.push(" ");
+        }
+    }
+
+    /// Adds inline content to the current line, without leading or repeated spaces.
+    fn push(&mut self, inline: &str) {
+        let at_start = self.line.is_empty() || self.line.ends_with(' ');
+        match inline.strip_prefix(' ') {
+            Some(rest) if at_start => self.line.push_str(rest),
+            _ => self.line.push_str(inline),
+        }
+    }
+
+    /// Ends the current paragraph, heading or list item.
+    fn flush(&mut self) {
+        let line = self
+            .line
+            .trim_end()
+            .to_string();
+        self.line.clear();
+        let is_empty = line
+            .trim_start_matches(' ')
+            .trim_start_matches(['-', '#'])
+            .trim()
+            .is_empty();
+        if is_empty {
+            return;
+        }
+        match self.list_depth {
+            0 => self.blocks.push(line),
+            _ => self.list.push(line),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fixtures() {
+        let fixtures = [
+            (
+                include_str!("fixtures/puzzles/part-one.html"),
+                include_str!("fixtures/puzzles/part-one.md"),
+            ),
+            (
+                include_str!("fixtures/puzzles/both-parts.html"),
+                include_str!("fixtures/puzzles/both-parts.md"),
+            ),
+        ];
+        for (html, markdown) in fixtures {
+            assert_eq!(to_markdown(html).as_deref(), Some(markdown));
+        }
+        assert_eq!(
+            to_markdown("<html><main><p>Not found</p></main></html>"),
+            None
+        );
+        assert_eq!(title(fixtures[1].1).as_deref(), Some("Counting Crates"));
+    }
+
+    #[test]
+    fn test_example() {
+        let example = example(include_str!("fixtures/puzzles/part-one.html")).unwrap();
+        assert_eq!(example.input(), "1000\n2000\n\n4000\n");
+        assert_eq!(
+            example
+                .answers
+                .part_one
+                .as_deref(),
+            Some("4000")
+        );
+        assert_eq!(example.answers.part_two, None);
+
+        let example = super::example(include_str!("fixtures/puzzles/both-parts.html")).unwrap();
+        assert_eq!(
+            example
+                .answers
+                .part_two
+                .as_deref(),
+            Some("4000")
+        );
+        assert!(super::example("<article class=\"day-desc\"><p>No code.</p></article>").is_none());
+    }
+
+    #[test]
+    fn test_inline() {
+        let convert = |html: &str| {
+            to_markdown(&format!(
+                "<article class=\"day-desc\"><p>{html}</p></article>"
+            ))
+            .unwrap()
+        };
+        assert_eq!(convert("a <code><em>7</em></code> b"), "a *`7`* b\n");
+        assert_eq!(convert("<em>x</em>, <code>a`b</code>"), "*x*, ``a`b``\n");
+        assert_eq!(convert("&lt;3 &amp; 2*2 &#x27;"), "<3 & 2\\*2 '\n");
+        assert_eq!(
+            convert("see <a href=\"/2022/day/1/input\">input</a>."),
+            "see [input](/2022/day/1/input).\n"
+        );
+    }
+}
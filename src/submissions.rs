@@ -0,0 +1,319 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use crate::input;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+const LOG_FILE: &str = ".submissions.csv";
+const HEADER: &str = "year,day,part,timestamp,outcome,answer";
+
+/// How the server judged a submitted answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    Correct,
+    TooHigh,
+    TooLow,
+    /// Wrong, without a hint in which direction.
+    Wrong,
+    /// An answer was submitted too recently. Holds the time left to wait, if the server said.
+    RateLimited(Option<Duration>),
+    /// The part was already solved, or part two was submitted before part one.
+    WrongLevel,
+    /// A response that was not understood, as its text.
+    Unknown(String),
+}
+
+impl Outcome {
+    /// A stable, machine-readable name for the outcome, as used in the log.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Outcome::Correct => "correct",
+            Outcome::TooHigh => "too_high",
+            Outcome::TooLow => "too_low",
+            Outcome::Wrong => "wrong",
+            Outcome::RateLimited(_) => "rate_limited",
+            Outcome::WrongLevel => "wrong_level",
+            Outcome::Unknown(_) => "unknown",
+        }
+    }
+
+    fn from_name(name: &str) -> Self {
+        match name {
+            "correct" => Outcome::Correct,
+            "too_high" => Outcome::TooHigh,
+            "too_low" => Outcome::TooLow,
+            "wrong" => Outcome::Wrong,
+            "rate_limited" => Outcome::RateLimited(None),
+            "wrong_level" => Outcome::WrongLevel,
+            other => Outcome::Unknown(other.to_string()),
+        }
+    }
+
+    /// Reads the outcome from the page the server responds to a submission with.
+    pub fn parse(page: &str) -> Self {
+        let text = article_text(page);
+        if text.contains("That's the right answer") {
+            Outcome::Correct
+        } else if text.contains("too high") {
+            Outcome::TooHigh
+        } else if text.contains("too low") {
+            Outcome::TooLow
+        } else if text.contains("That's not the right answer") {
+            Outcome::Wrong
+        } else if text.contains("You gave an answer too recently") {
+            Outcome::RateLimited(parse_wait(&text))
+        } else if text.contains("You don't seem to be solving the right level") {
+            Outcome::WrongLevel
+        } else {
+            Outcome::Unknown(text)
+        }
+    }
+}
+
+/// The text of the `<article>` element of a page, without markup.
+fn article_text(page: &str) -> String {
+    let article = match (page.find("<article"), page.find("</article>")) {
+        (Some(start), Some(end)) if start < end => &page[start..end],
+        _ => page,
+    };
+
+    let mut text = String::new();
+    let mut in_tag = false;
+    for c in article.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            c if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    text.split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Reads the wait time from e.g. "You have 1m 5s left to wait."
+fn parse_wait(text: &str) -> Option<Duration> {
+    let wait = text
+        .split("You have ")
+        .nth(1)?
+        .split(" left to wait")
+        .next()?;
+    let mut seconds = 0;
+    for amount in wait.split_whitespace() {
+        let (number, unit) = amount.split_at(amount.find(|c: char| !c.is_ascii_digit())?);
+        let number: u64 = number.parse().ok()?;
+        seconds += match unit {
+            "h" => number * 3600,
+            "m" => number * 60,
+            "s" => number,
+            _ => return None,
+        };
+    }
+    Some(Duration::from_secs(seconds))
+}
+
+/// One submitted answer, as stored in the submission log.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Entry {
+    pub year: i32,
+    pub day: u8,
+    pub part: u8,
+    /// Seconds since the unix epoch.
+    pub timestamp: u64,
+    pub outcome: Outcome,
+    pub answer: String,
+}
+
+impl Entry {
+    pub fn new(year: i32, day: u8, part: u8, answer: &str, outcome: Outcome) -> Self {
+        Self {
+            year,
+            day,
+            part,
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or_default(),
+            outcome,
+            answer: answer.to_string(),
+        }
+    }
+
+    /// The answer comes last, so it may contain commas.
+    fn to_csv(&self) -> String {
+        format!(
+            "{},{},{},{},{},{}",
+            self.year,
+            self.day,
+            self.part,
+            self.timestamp,
+            self.outcome.name(),
+            self.answer
+        )
+    }
+
+    fn from_csv(line: &str) -> Option<Self> {
+        let fields: Vec<&str> = line.splitn(6, ',').collect();
+        if fields.len() != 6 {
+            return None;
+        }
+        Some(Self {
+            year: fields[0].parse().ok()?,
+            day: fields[1].parse().ok()?,
+            part: fields[2].parse().ok()?,
+            timestamp: fields[3].parse().ok()?,
+            outcome: Outcome::from_name(fields[4]),
+            answer: fields[5].to_string(),
+        })
+    }
+}
+
+fn log_path() -> PathBuf {
+    input::project_dir().join(LOG_FILE)
+}
+
+/// Reads all entries from the submission log. A missing file is an empty log.
+pub fn load() -> io::Result<Vec<Entry>> {
+    match fs::read_to_string(log_path()) {
+        Ok(contents) => Ok(contents
+            .lines()
+            .filter_map(Entry::from_csv)
+            .collect()),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(vec![]),
+        Err(e) => Err(e),
+    }
+}
+
+/// Appends `entry` to the submission log, creating it with a header if needed.
+pub fn append(entry: &Entry) -> io::Result<()> {
+    let path = log_path();
+    let is_new = !path.exists();
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)?;
+    if is_new {
+        writeln!(file, "{HEADER}")?;
+    }
+    writeln!(file, "{}", entry.to_csv())
+}
+
+/// Explains why submitting `answer` is pointless given the earlier submissions in `log`, or
+/// returns `None` if it is worth a try.
+pub fn check(log: &[Entry], year: i32, day: u8, part: u8, answer: &str) -> Option<String> {
+    let attempts: Vec<&Entry> = log
+        .iter()
+        .filter(|entry| entry.year == year && entry.day == day && entry.part == part)
+        .collect();
+
+    if let Some(entry) = attempts
+        .iter()
+        .find(|entry| entry.outcome == Outcome::Correct)
+    {
+        return Some(format!(
+            "this part was already solved with {}",
+            entry.answer
+        ));
+    }
+
+    let wrong = |entry: &Entry| {
+        matches!(
+            entry.outcome,
+            Outcome::TooHigh | Outcome::TooLow | Outcome::Wrong
+        )
+    };
+    if attempts
+        .iter()
+        .any(|entry| wrong(entry) && entry.answer == answer)
+    {
+        return Some(format!(
+            "{answer} was already submitted and is not the right answer"
+        ));
+    }
+
+    let value: i128 = answer.parse().ok()?;
+    let bound = |outcome: Outcome| {
+        attempts
+            .iter()
+            .filter(move |entry| entry.outcome == outcome)
+            .filter_map(|entry| {
+                entry
+                    .answer
+                    .parse::<i128>()
+                    .ok()
+            })
+    };
+    if let Some(high) = bound(Outcome::TooHigh)
+        .filter(|high| value >= *high)
+        .min()
+    {
+        return Some(format!("{answer} is too high, {high} already was"));
+    }
+    if let Some(low) = bound(Outcome::TooLow)
+        .filter(|low| value <= *low)
+        .max()
+    {
+        return Some(format!("{answer} is too low, {low} already was"));
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_outcome() {
+        let page =
+            |text: &str| format!("<html><main><article><p>{text}</p></article></main></html>");
+        assert_eq!(
+            Outcome::parse(&page(
+                "That's the right answer!  You are <em>one gold star</em> closer."
+            )),
+            Outcome::Correct
+        );
+        assert_eq!(
+            Outcome::parse(&page(
+                "That's not the right answer; your answer is too high."
+            )),
+            Outcome::TooHigh
+        );
+        assert_eq!(
+            Outcome::parse(&page("That's not the right answer.")),
+            Outcome::Wrong
+        );
+        assert_eq!(
+            Outcome::parse(&page(
+                "You gave an answer too recently. You have 1m 5s left to wait."
+            )),
+            Outcome::RateLimited(Some(Duration::from_secs(65)))
+        );
+        assert_eq!(
+            Outcome::parse(&page("You don't seem to be solving the right level.")),
+            Outcome::WrongLevel
+        );
+    }
+
+    #[test]
+    fn test_check() {
+        let log = vec![
+            Entry::new(2022, 1, 1, "500", Outcome::TooHigh),
+            Entry::new(2022, 1, 1, "100", Outcome::TooLow),
+            Entry::new(2022, 1, 1, "300", Outcome::Wrong),
+            Entry::new(2022, 1, 2, "7", Outcome::Correct),
+        ];
+        assert_eq!(Entry::from_csv(&log[0].to_csv()).as_ref(), Some(&log[0]));
+
+        assert_eq!(check(&log, 2022, 1, 1, "250"), None);
+        assert!(check(&log, 2022, 1, 1, "300").is_some());
+        assert!(check(&log, 2022, 1, 1, "600").is_some());
+        assert!(check(&log, 2022, 1, 1, "100").is_some());
+        assert!(check(&log, 2022, 1, 2, "8").is_some());
+        assert_eq!(check(&log, 2021, 1, 1, "600"), None);
+    }
+}
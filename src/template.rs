@@ -0,0 +1,154 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use crate::{config, input};
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+/// The template used when `--template` is not given.
+pub const DEFAULT: &str = "default";
+
+/// The built-in default template, used if `templates/default.rs` was removed.
+const BUILT_IN: &str = include_str!("../templates/default.rs");
+
+/// The values a template can refer to as `{{day}}`, `{{day_padded}}`, `{{year}}` and `{{title}}`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Vars {
+    pub day: u8,
+    pub year: i32,
+    pub title: String,
+}
+
+impl Vars {
+    fn get(&self, name: &str) -> Option<String> {
+        match name {
+            "day" => Some(self.day.to_string()),
+            "day_padded" => Some(format!("{:02}", self.day)),
+            "year" => Some(self.year.to_string()),
+            "title" => Some(self.title.clone()),
+            _ => None,
+        }
+    }
+}
+
+/// The directory templates are loaded from, `templates` unless `aoc.toml` says otherwise.
+pub fn templates_dir() -> PathBuf {
+    match &config::get().paths.templates {
+        Some(dir) => config::resolve(dir),
+        None => input::project_dir().join("templates"),
+    }
+}
+
+/// The names of the templates in [`templates_dir`], sorted.
+pub fn available() -> Vec<String> {
+    let mut names: Vec<String> = fs::read_dir(templates_dir())
+        .into_iter()
+        .flatten()
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.extension()
+                .is_some_and(|ext| ext == "rs")
+        })
+        .filter_map(|path| {
+            Some(
+                path.file_stem()?
+                    .to_string_lossy()
+                    .into_owned(),
+            )
+        })
+        .collect();
+    names.sort();
+    names
+}
+
+/// Reads the template `name` from `templates/<name>.rs`.
+pub fn load(name: &str) -> Result<String, String> {
+    let path = templates_dir().join(format!("{name}.rs"));
+    match fs::read_to_string(&path) {
+        Ok(template) => Ok(template),
+        Err(e) if e.kind() == io::ErrorKind::NotFound && name == DEFAULT => {
+            Ok(BUILT_IN.to_string())
+        }
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Err(format!(
+            "there is no template \"{name}\" in \"{}\", available: {}",
+            templates_dir().display(),
+            available().join(", ")
+        )),
+        Err(e) => Err(format!("could not read \"{}\": {e}", path.display())),
+    }
+}
+
+/// Replaces every `{{name}}` in `template` with the value of `name` in `vars`.
+///
+/// Only lowercase names are placeholders, so escaped braces in format strings such as `"{{}}"`
+/// are left alone. An unknown name is an error, as it is most likely a typo.
+pub fn render(template: &str, vars: &Vars) -> Result<String, String> {
+    let mut rendered = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find("{{") {
+        let after = &rest[start + 2..];
+        let placeholder = after
+            .find("}}")
+            .map(|end| (after[..end].trim(), end));
+        match placeholder {
+            Some((name, end))
+                if !name.is_empty()
+                    && name
+                        .chars()
+                        .all(|c| c.is_ascii_lowercase() || c == '_') =>
+            {
+                let value = vars.get(name).ok_or_else(|| {
+                    let line = template[..template.len() - rest.len() + start].matches('\n').count() + 1;
+                    format!("unknown placeholder {{{{{name}}}}} on line {line}, expected one of: day, day_padded, year, title")
+                })?;
+                rendered.push_str(&rest[..start]);
+                rendered.push_str(&value);
+                rest = &after[end + 2..];
+            }
+            _ => {
+                rendered.push_str(&rest[..start + 2]);
+                rest = after;
+            }
+        }
+    }
+    rendered.push_str(rest);
+    Ok(rendered)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render() {
+        let vars = Vars {
+            day: 7,
+            year: 2022,
+            title: "No Space Left On Device".to_string(),
+        };
+        assert_eq!(
+            render(
+                "struct Day{{day_padded}}; // {{ title }}, {{year}}/{{day}}",
+                &vars
+            ),
+            Ok("struct Day07; // No Space Left On Device, 2022/7".to_string())
+        );
+        assert_eq!(
+            render("DAY format!(\"{{}}\") {{Day}}", &vars),
+            Ok("DAY format!(\"{{}}\") {{Day}}".to_string())
+        );
+        assert!(render("\n{{dya}}", &vars)
+            .unwrap_err()
+            .contains("line 2"));
+
+        for name in available() {
+            assert!(
+                render(&load(&name).unwrap(), &vars).is_ok(),
+                "template {name}"
+            );
+        }
+    }
+}
@@ -0,0 +1,315 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use std::env;
+use std::fmt::{self, Display};
+use std::fs::OpenOptions;
+use std::io::{self, Write};
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::{Mutex, OnceLock};
+
+/// Selects the trace statements that are written, e.g. `AOC_LOG=debug` or `AOC_LOG=info,9=trace`.
+pub const FILTER_VAR: &str = "AOC_LOG";
+/// Writes trace statements to the given file instead of stderr.
+pub const FILE_VAR: &str = "AOC_LOG_FILE";
+
+/// How detailed a trace statement is, from least to most.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level {
+    Error,
+    Warn,
+    Info,
+    Debug,
+    Trace,
+}
+
+impl Level {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Level::Error => "error",
+            Level::Warn => "warn",
+            Level::Info => "info",
+            Level::Debug => "debug",
+            Level::Trace => "trace",
+        }
+    }
+}
+
+/// Parses a level, or `off` for none at all.
+fn parse_level(s: &str) -> Result<Option<Level>, String> {
+    match s {
+        "off" => Ok(None),
+        "error" => Ok(Some(Level::Error)),
+        "warn" => Ok(Some(Level::Warn)),
+        "info" => Ok(Some(Level::Info)),
+        "debug" => Ok(Some(Level::Debug)),
+        "trace" => Ok(Some(Level::Trace)),
+        _ => Err(format!(
+            "unknown level \"{s}\", expected one of: off, error, warn, info, debug, trace"
+        )),
+    }
+}
+
+/// The most detailed level to write, overall and for single days.
+///
+/// Written as comma-separated directives: a level applies to all days, `<day>=<level>` to a single
+/// one. `warn,9=trace` writes everything for day 9 and only warnings and errors for the others.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Filter {
+    default: Option<Level>,
+    days: Vec<(u8, Option<Level>)>,
+}
+
+impl Filter {
+    pub fn enabled(&self, day: u8, level: Level) -> bool {
+        let max = self
+            .days
+            .iter()
+            .rev()
+            .find(|(d, _)| *d == day)
+            .map_or(self.default, |(_, max)| *max);
+        max.is_some_and(|max| level <= max)
+    }
+
+    /// Whether any statement is written at all, for any day.
+    pub fn is_off(&self) -> bool {
+        self.default.is_none()
+            && self
+                .days
+                .iter()
+                .all(|(_, max)| max.is_none())
+    }
+}
+
+impl FromStr for Filter {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut filter = Filter::default();
+        for directive in s
+            .split(',')
+            .map(str::trim)
+            .filter(|d| !d.is_empty())
+        {
+            match directive.split_once('=') {
+                Some((day, level)) => {
+                    let day = day
+                        .trim()
+                        .parse()
+                        .map_err(|_| format!("expected a day in \"{directive}\""))?;
+                    filter
+                        .days
+                        .push((day, parse_level(level.trim())?));
+                }
+                None => filter.default = parse_level(directive)?,
+            }
+        }
+        Ok(filter)
+    }
+}
+
+impl Display for Filter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = |level: Option<Level>| level.map_or("off", |level| level.name());
+        write!(f, "{}", name(self.default))?;
+        for (day, level) in &self.days {
+            write!(f, ",{day}={}", name(*level))?;
+        }
+        Ok(())
+    }
+}
+
+/// Tracing settings from the command line, set by `--log <filter>` and `--log-file <path>`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TraceOptions {
+    pub filter: Option<Filter>,
+    pub file: Option<PathBuf>,
+}
+
+struct Tracer {
+    filter: Filter,
+    file: Option<PathBuf>,
+    output: Mutex<Box<dyn Write + Send>>,
+}
+
+impl Tracer {
+    fn new(filter: Filter, file: Option<PathBuf>) -> Self {
+        let output: Box<dyn Write + Send> = match &file {
+            Some(path) => match OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(path)
+            {
+                Ok(file) => Box::new(file),
+                Err(e) => {
+                    eprintln!("Could not open trace file \"{}\": {e}", path.display());
+                    Box::new(io::stderr())
+                }
+            },
+            None => Box::new(io::stderr()),
+        };
+        Self {
+            filter,
+            file,
+            output: Mutex::new(output),
+        }
+    }
+
+    fn from_env(options: &TraceOptions) -> Self {
+        let filter = options
+            .filter
+            .clone()
+            .unwrap_or_else(|| match env::var(FILTER_VAR) {
+                Ok(filter) => filter
+                    .parse()
+                    .unwrap_or_else(|e| {
+                        eprintln!("Ignoring {FILTER_VAR}: {e}");
+                        Filter::default()
+                    }),
+                Err(_) => Filter::default(),
+            });
+        let file = options
+            .file
+            .clone()
+            .or_else(|| {
+                env::var_os(FILE_VAR)
+                    .filter(|file| !file.is_empty())
+                    .map(PathBuf::from)
+            });
+        Self::new(filter, file)
+    }
+}
+
+static TRACER: OnceLock<Tracer> = OnceLock::new();
+static DAY: AtomicU8 = AtomicU8::new(0);
+
+fn tracer() -> &'static Tracer {
+    TRACER.get_or_init(|| Tracer::from_env(&TraceOptions::default()))
+}
+
+/// Sets up tracing. Flags take precedence over [`FILTER_VAR`] and [`FILE_VAR`]. Without a call
+/// to this, tracing is set up from the environment alone.
+///
+/// Warns if tracing is enabled in a release build, which leaves trace statements out, see
+/// [`log!`](crate::log).
+pub fn init(options: &TraceOptions) {
+    let tracer = Tracer::from_env(options);
+    if !cfg!(debug_assertions) && !tracer.filter.is_off() {
+        eprintln!(
+            "Trace statements are left out of release builds, --log and {FILTER_VAR} have no effect."
+        );
+    }
+    if TRACER.set(tracer).is_err() {
+        eprintln!("Tracing was already set up, ignoring --log and --log-file.");
+    }
+}
+
+/// The environment that makes another process trace like this one, e.g. a worker.
+pub fn env_vars() -> Vec<(&'static str, String)> {
+    let tracer = tracer();
+    let mut vars = vec![(FILTER_VAR, tracer.filter.to_string())];
+    if let Some(file) = &tracer.file {
+        vars.push((FILE_VAR, file.display().to_string()));
+    }
+    vars
+}
+
+/// Sets the day that trace statements are filtered and labelled by.
+pub fn set_day(day: u8) {
+    DAY.store(day, Ordering::Relaxed);
+}
+
+/// Whether statements at `level` are written for the current day. Used by [`log!`](crate::log).
+pub fn enabled(level: Level) -> bool {
+    tracer()
+        .filter
+        .enabled(DAY.load(Ordering::Relaxed), level)
+}
+
+/// Writes a trace statement. Used by [`log!`](crate::log).
+pub fn write(level: Level, args: fmt::Arguments) {
+    let mut output = match tracer().output.lock() {
+        Ok(output) => output,
+        Err(poisoned) => poisoned.into_inner(),
+    };
+    let _ = writeln!(
+        output,
+        "[day {:02} {}] {args}",
+        DAY.load(Ordering::Relaxed),
+        level.name()
+    );
+}
+
+/// Writes a trace statement at `level` if the filter enables it for the current day.
+///
+/// Release builds, which benchmarks use, leave trace statements out entirely, so they cost
+/// nothing there. Use the level-specific macros such as [`debug!`](crate::debug) instead of
+/// calling this directly.
+#[macro_export]
+macro_rules! log {
+    ($level:expr, $($arg:tt)+) => {
+        if cfg!(debug_assertions) && $crate::trace::enabled($level) {
+            $crate::trace::write($level, format_args!($($arg)+));
+        }
+    };
+}
+
+#[macro_export]
+macro_rules! error {
+    ($($arg:tt)+) => { $crate::log!($crate::trace::Level::Error, $($arg)+) };
+}
+
+#[macro_export]
+macro_rules! warn {
+    ($($arg:tt)+) => { $crate::log!($crate::trace::Level::Warn, $($arg)+) };
+}
+
+#[macro_export]
+macro_rules! info {
+    ($($arg:tt)+) => { $crate::log!($crate::trace::Level::Info, $($arg)+) };
+}
+
+#[macro_export]
+macro_rules! debug {
+    ($($arg:tt)+) => { $crate::log!($crate::trace::Level::Debug, $($arg)+) };
+}
+
+#[macro_export]
+macro_rules! trace {
+    ($($arg:tt)+) => { $crate::log!($crate::trace::Level::Trace, $($arg)+) };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_filter() {
+        let filter: Filter = "warn, 9=trace,11=off"
+            .parse()
+            .unwrap();
+        assert!(filter.enabled(1, Level::Warn));
+        assert!(!filter.enabled(1, Level::Info));
+        assert!(filter.enabled(9, Level::Trace));
+        assert!(!filter.enabled(11, Level::Error));
+        assert!(!filter.is_off());
+        assert_eq!(filter.to_string(), "warn,9=trace,11=off");
+        assert_eq!(filter.to_string().parse(), Ok(filter));
+
+        assert!(!Filter::default().enabled(1, Level::Error));
+        assert!(Filter::default().is_off());
+        assert!("11=off"
+            .parse::<Filter>()
+            .unwrap()
+            .is_off());
+        assert!("verbose"
+            .parse::<Filter>()
+            .is_err());
+        assert!("x=debug"
+            .parse::<Filter>()
+            .is_err());
+    }
+}
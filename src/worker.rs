@@ -0,0 +1,505 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use crate::input::InputSource;
+use crate::memory::{self, MemoryStats};
+use crate::parse::ParseError;
+use crate::{answers, trace, DayResult, PartResult, Progress, Registry, Status};
+use std::env;
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::process::{self, Command, Stdio};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Makes the runner binary solve a single day as a worker, e.g. `advent_of_code --worker 5`.
+pub const WORKER_ARG: &str = "--worker";
+
+/// Starts every line of the worker protocol, so it can be told apart from what solutions print.
+const PREFIX: &str = "\x1eaoc\t";
+
+/// Limits for each day run by `cargo all`. A day that exceeds them is stopped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Limits {
+    /// How long a day may take as a whole.
+    pub day_timeout: Option<Duration>,
+    /// How long parsing or either of the parts may take.
+    pub part_timeout: Option<Duration>,
+    /// How much heap memory a day may use, in bytes.
+    pub memory: Option<usize>,
+}
+
+impl Default for Limits {
+    fn default() -> Self {
+        Self {
+            day_timeout: Some(Duration::from_secs(60)),
+            part_timeout: None,
+            memory: None,
+        }
+    }
+}
+
+impl Limits {
+    /// The defaults, with those set in the `[run]` table of `aoc.toml` instead.
+    pub fn configured() -> Self {
+        let run = &crate::config::get().run;
+        let defaults = Self::default();
+        Self {
+            day_timeout: run
+                .day_timeout
+                .or(defaults.day_timeout),
+            part_timeout: run
+                .timeout
+                .or(defaults.part_timeout),
+            memory: run
+                .memory_limit
+                .or(defaults.memory),
+        }
+    }
+}
+
+/// A line of the worker protocol.
+#[derive(Debug, PartialEq, Eq)]
+enum Message {
+    Parsed(Duration),
+    Part(PartResult),
+}
+
+fn escape(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('\t', "\\t")
+        .replace('\n', "\\n")
+        .replace('\r', "\\r")
+}
+
+fn unescape(value: &str) -> String {
+    let mut unescaped = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => match chars.next() {
+                Some('t') => unescaped.push('\t'),
+                Some('n') => unescaped.push('\n'),
+                Some('r') => unescaped.push('\r'),
+                Some(c) => unescaped.push(c),
+                None => {}
+            },
+            c => unescaped.push(c),
+        }
+    }
+    unescaped
+}
+
+fn encode_status(status: &Status) -> Vec<String> {
+    let mut fields = vec![status.name().to_string()];
+    match status {
+        Status::Panicked(message) | Status::NoInput(message) | Status::CompileError(message) => {
+            fields.push(escape(message))
+        }
+        Status::Invalid(error) => fields.extend([
+            escape(&error.message),
+            escape(&error.text),
+            error.line.to_string(),
+            error.column.to_string(),
+            escape(&error.source_line),
+        ]),
+        Status::TimedOut(limit) => fields.push(limit.as_nanos().to_string()),
+        Status::OutOfMemory(limit) => fields.push(limit.to_string()),
+        Status::Solved | Status::Unsolved | Status::Missing => {}
+    }
+    fields
+}
+
+fn decode_status(fields: &[&str]) -> Option<Status> {
+    let status = match fields {
+        ["solved"] => Status::Solved,
+        ["unsolved"] => Status::Unsolved,
+        ["missing"] => Status::Missing,
+        ["panicked", message] => Status::Panicked(unescape(message)),
+        ["no_input", message] => Status::NoInput(unescape(message)),
+        ["compile_error", message] => Status::CompileError(unescape(message)),
+        ["invalid_input", message, text, line, column, source_line] => {
+            let mut error = ParseError::new(&unescape(text), unescape(message));
+            error.line = line.parse().ok()?;
+            error.column = column.parse().ok()?;
+            error.source_line = unescape(source_line);
+            Status::Invalid(error)
+        }
+        ["timed_out", limit] => Status::TimedOut(Duration::from_nanos(limit.parse().ok()?)),
+        ["out_of_memory", limit] => Status::OutOfMemory(limit.parse().ok()?),
+        _ => return None,
+    };
+    Some(status)
+}
+
+fn encode(progress: &Progress) -> String {
+    match progress {
+        Progress::Parsed(elapsed) => format!("{PREFIX}parsed\t{}", elapsed.as_nanos()),
+        Progress::Part(result) => {
+            let answer = match &result.answer {
+                Some(answer) => format!("+{}", escape(answer)),
+                None => "-".to_string(),
+            };
+            let memory = match result.memory {
+                Some(stats) => format!(
+                    "{},{},{}",
+                    stats.peak_bytes, stats.allocated_bytes, stats.allocations
+                ),
+                None => "-".to_string(),
+            };
+            format!(
+                "{PREFIX}part\t{}\t{}\t{answer}\t{memory}\t{}",
+                result.part,
+                result.elapsed.as_nanos(),
+                encode_status(&result.status).join("\t")
+            )
+        }
+    }
+}
+
+/// Reads a protocol line, or returns `None` for anything else.
+fn decode(line: &str) -> Option<Message> {
+    let fields: Vec<&str> = line
+        .strip_prefix(PREFIX)?
+        .split('\t')
+        .collect();
+    match fields.as_slice() {
+        ["parsed", elapsed] => Some(Message::Parsed(Duration::from_nanos(elapsed.parse().ok()?))),
+        ["part", part, elapsed, answer, memory, status @ ..] => {
+            let memory = match *memory {
+                "-" => None,
+                memory => {
+                    let stats: Vec<usize> = memory
+                        .split(',')
+                        .map(|n| n.parse().ok())
+                        .collect::<Option<_>>()?;
+                    match stats.as_slice() {
+                        [peak_bytes, allocated_bytes, allocations] => Some(MemoryStats {
+                            peak_bytes: *peak_bytes,
+                            allocated_bytes: *allocated_bytes,
+                            allocations: *allocations,
+                        }),
+                        _ => return None,
+                    }
+                }
+            };
+            Some(Message::Part(PartResult {
+                part: part.parse().ok()?,
+                status: decode_status(status)?,
+                answer: answer
+                    .strip_prefix('+')
+                    .map(unescape),
+                elapsed: Duration::from_nanos(elapsed.parse().ok()?),
+                memory,
+                verdict: None,
+            }))
+        }
+        _ => None,
+    }
+}
+
+/// The body of a worker: reads the input from stdin, solves the day given after [`WORKER_ARG`]
+/// and reports each phase on stdout as soon as it finishes.
+pub fn serve(registry: &Registry) -> ! {
+    let mut args = pico_args::Arguments::from_env();
+    let parsed: Result<(u8, Option<usize>, Option<i32>), pico_args::Error> = args
+        .value_from_str(WORKER_ARG)
+        .and_then(|day| {
+            Ok((
+                day,
+                args.opt_value_from_str("--memory-limit")?,
+                args.opt_value_from_str("--year")?,
+            ))
+        });
+    let (day, memory_limit, year) = parsed.unwrap_or_else(|e| {
+        eprintln!("Failed to process worker arguments: {e}");
+        process::exit(1);
+    });
+    serve_day(registry, day, memory_limit, year)
+}
+
+fn serve_day(registry: &Registry, day: u8, memory_limit: Option<usize>, year: Option<i32>) -> ! {
+    if let Some(year) = year {
+        crate::input::set_year(year);
+    }
+
+    let Some(solution) = registry.get(day) else {
+        eprintln!("Day {day} has no solution to run.");
+        process::exit(1);
+    };
+
+    let mut input = String::new();
+    if let Err(e) = io::stdin().read_to_string(&mut input) {
+        eprintln!("Could not read input: {e}");
+        process::exit(1);
+    }
+
+    if let Some(limit) = memory_limit {
+        memory::set_limit(limit);
+    }
+
+    solution.run_observed(&input, &mut |progress| {
+        let mut stdout = io::stdout().lock();
+        // If the runner went away, there is no one left to report to.
+        let _ = writeln!(stdout, "{}", encode(&progress)).and_then(|_| stdout.flush());
+    });
+    process::exit(0);
+}
+
+/// Solves `day` in a worker process that is stopped once it exceeds `limits`. Parts that did not
+/// finish are reported as [`Status::TimedOut`] or [`Status::OutOfMemory`].
+pub fn solve(registry: &Registry, day: u8, source: &InputSource, limits: &Limits) -> DayResult {
+    if let Some(status) = registry.unavailable(day) {
+        return DayResult::failed(status);
+    }
+
+    let mut result = match source.read(day) {
+        Ok(input) => supervise(worker_command(day, limits), &input, limits),
+        Err(e) => DayResult::failed(Status::NoInput(e.to_string())),
+    };
+    if source.is_puzzle() {
+        answers::verify(day, &mut result.parts);
+    }
+    result
+}
+
+/// The command that starts a worker for `day`, a copy of this binary.
+fn worker_command(day: u8, limits: &Limits) -> Command {
+    let mut command = Command::new(env::current_exe().unwrap_or_else(|_| "advent_of_code".into()));
+    command
+        .arg(WORKER_ARG)
+        .arg(day.to_string())
+        .args(crate::input::year_args())
+        .envs(trace::env_vars());
+    if let Some(limit) = limits.memory {
+        command
+            .arg("--memory-limit")
+            .arg(limit.to_string());
+    }
+    command
+}
+
+/// Runs the worker that `command` starts, feeding it `input`, until it reports both parts or
+/// exceeds `limits`.
+fn supervise(mut command: Command, input: &str, limits: &Limits) -> DayResult {
+    command
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped());
+    let mut child = match command.spawn() {
+        Ok(child) => child,
+        Err(e) => {
+            return DayResult::failed(Status::Panicked(format!("could not start a worker: {e}")))
+        }
+    };
+
+    // The worker reads all of its input before it writes anything, so this cannot deadlock. It
+    // only fails if the worker died early, which waiting for it below reports.
+    if let Some(mut stdin) = child.stdin.take() {
+        let _ = stdin.write_all(input.as_bytes());
+    }
+
+    let (sender, receiver) = mpsc::channel();
+    if let Some(stdout) = child.stdout.take() {
+        thread::spawn(move || {
+            for line in BufReader::new(stdout)
+                .lines()
+                .map_while(Result::ok)
+            {
+                if sender.send(line).is_err() {
+                    break;
+                }
+            }
+        });
+    }
+
+    let started = Instant::now();
+    let mut phase_started = started;
+    let mut parse_elapsed = Duration::ZERO;
+    let mut parts: Vec<PartResult> = Vec::with_capacity(2);
+
+    let stopped = loop {
+        let deadline = [
+            limits
+                .day_timeout
+                .map(|limit| (started + limit, limit)),
+            limits
+                .part_timeout
+                .map(|limit| (phase_started + limit, limit)),
+        ]
+        .into_iter()
+        .flatten()
+        .min_by_key(|(deadline, _)| *deadline);
+
+        let line = match deadline {
+            Some((deadline, limit)) => {
+                match receiver.recv_timeout(deadline.saturating_duration_since(Instant::now())) {
+                    Ok(line) => line,
+                    Err(RecvTimeoutError::Timeout) => break Some(Status::TimedOut(limit)),
+                    Err(RecvTimeoutError::Disconnected) => break None,
+                }
+            }
+            None => match receiver.recv() {
+                Ok(line) => line,
+                Err(_) => break None,
+            },
+        };
+
+        // Solutions may print without a trailing newline, so the protocol can start mid-line.
+        // What they print goes to stderr, so that it does not mix with the results on stdout.
+        let (printed, message) = match line.find(PREFIX) {
+            Some(index) => (&line[..index], decode(&line[index..])),
+            None => (line.as_str(), None),
+        };
+        if !printed.is_empty() {
+            eprintln!("{printed}");
+        }
+
+        match message {
+            Some(Message::Parsed(elapsed)) => parse_elapsed = elapsed,
+            Some(Message::Part(result)) => parts.push(result),
+            None => continue,
+        }
+        if parts.len() == 2 {
+            break None;
+        }
+        phase_started = Instant::now();
+    };
+
+    // The status of the parts that did not finish, if any.
+    let status = match stopped {
+        Some(status) => {
+            let _ = child.kill();
+            let _ = child.wait();
+            status
+        }
+        None => match (child.wait(), limits.memory) {
+            (Ok(exit), Some(limit)) if exit.code() == Some(memory::LIMIT_EXIT_CODE) => {
+                Status::OutOfMemory(limit)
+            }
+            (Ok(exit), _) => Status::Panicked(format!("the worker exited unexpectedly ({exit})")),
+            (Err(e), _) => Status::Panicked(format!("could not wait for the worker: {e}")),
+        },
+    };
+
+    while parts.len() < 2 {
+        parts.push(PartResult::failed(parts.len() as u8 + 1, status.clone()));
+    }
+    DayResult {
+        parse_elapsed,
+        parts: [parts.remove(0), parts.remove(0)],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Day, ParseError, Solution};
+
+    /// Set to the memory limit, if any, when the tests below run [`test_worker`] as a worker.
+    const TEST_WORKER_VAR: &str = "AOC_TEST_WORKER";
+
+    /// Allocates a mebibyte in part one and takes its time in part two.
+    struct Greedy;
+
+    impl Solution for Greedy {
+        const DAY: u8 = 1;
+
+        type Input = ();
+        type PartOne = usize;
+        type PartTwo = usize;
+
+        fn parse(_: &str) -> Result<(), ParseError> {
+            Ok(())
+        }
+
+        fn part_one(_: &()) -> Option<usize> {
+            Some(vec![1u8; 1024 * 1024].len())
+        }
+
+        fn part_two(_: &()) -> Option<usize> {
+            thread::sleep(Duration::from_secs(30));
+            None
+        }
+    }
+
+    /// Serves [`Greedy`] when this test binary is started as a worker, and does nothing otherwise.
+    #[test]
+    fn test_worker() {
+        if let Ok(memory_limit) = env::var(TEST_WORKER_VAR) {
+            let registry = Registry::new(vec![Day::new::<Greedy>()]);
+            serve_day(&registry, 1, memory_limit.parse().ok(), None);
+        }
+    }
+
+    fn supervise_greedy(limits: &Limits) -> DayResult {
+        let mut command = Command::new(env::current_exe().unwrap());
+        command
+            .args(["worker::tests::test_worker", "--exact", "--nocapture"])
+            .env(
+                TEST_WORKER_VAR,
+                limits
+                    .memory
+                    .map_or(String::new(), |limit| limit.to_string()),
+            );
+        supervise(command, "", limits)
+    }
+
+    #[test]
+    fn test_limits() {
+        let limits = Limits {
+            day_timeout: None,
+            part_timeout: Some(Duration::from_secs(2)),
+            memory: None,
+        };
+        let [part_one, part_two] = supervise_greedy(&limits).parts;
+        assert_eq!(part_one.answer, Some("1048576".to_string()));
+        assert_eq!(part_two.status, Status::TimedOut(Duration::from_secs(2)));
+
+        let limits = Limits {
+            memory: Some(64 * 1024),
+            ..limits
+        };
+        let [part_one, part_two] = supervise_greedy(&limits).parts;
+        assert_eq!(part_one.status, Status::OutOfMemory(64 * 1024));
+        assert_eq!(part_two.status, Status::OutOfMemory(64 * 1024));
+    }
+
+    #[test]
+    fn test_protocol() {
+        let solved = PartResult {
+            part: 2,
+            status: Status::Solved,
+            answer: Some("##..\n\t\\".to_string()),
+            elapsed: Duration::from_nanos(1_250),
+            memory: Some(MemoryStats {
+                peak_bytes: 4096,
+                allocated_bytes: 6144,
+                allocations: 3,
+            }),
+            verdict: None,
+        };
+        let encoded = encode(&Progress::Part(&solved));
+        assert_eq!(encoded.lines().count(), 1);
+        assert_eq!(decode(&encoded), Some(Message::Part(solved)));
+
+        let input = "R 4\nU x\n";
+        let error = crate::parse::parse_field::<u32>(&input[6..7])
+            .unwrap_err()
+            .locate(input);
+        let invalid = PartResult::failed(1, Status::Invalid(error));
+        assert_eq!(
+            decode(&encode(&Progress::Part(&invalid))),
+            Some(Message::Part(invalid))
+        );
+
+        let parsed = encode(&Progress::Parsed(Duration::from_nanos(500)));
+        assert_eq!(
+            decode(&parsed),
+            Some(Message::Parsed(Duration::from_nanos(500)))
+        );
+        assert_eq!(decode("\x1eaoc\tpart\t1"), None);
+        assert_eq!(decode("a line printed by a solution"), None);
+    }
+}
@@ -0,0 +1,31 @@
+//! Day {{day}}: {{title}}
+//! https://adventofcode.com/{{year}}/day/{{day}}
+use advent_of_code::{ParseError, Solution};
+
+pub struct Day{{day_padded}};
+
+impl Solution for Day{{day_padded}} {
+    const DAY: u8 = {{day}};
+
+    type Input = String;
+    type PartOne = u32;
+    type PartTwo = u32;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        Ok(input.to_string())
+    }
+
+    fn part_one(input: &Self::Input) -> Option<u32> {
+        None
+    }
+
+    fn part_two(input: &Self::Input) -> Option<u32> {
+        None
+    }
+}
+
+fn main() {
+    advent_of_code::solve!(Day{{day_padded}});
+}
+
+advent_of_code::example_tests!(Day{{day_padded}});
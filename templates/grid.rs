@@ -0,0 +1,68 @@
+//! Day {{day}}: {{title}}
+//! https://adventofcode.com/{{year}}/day/{{day}}
+use advent_of_code::{ParseError, Solution};
+
+pub struct Day{{day_padded}};
+
+/// A rectangular grid of characters, stored row by row.
+#[derive(Debug, Clone)]
+pub struct Grid {
+    width: usize,
+    height: usize,
+    cells: Vec<u8>,
+}
+
+impl Grid {
+    pub fn get(&self, x: usize, y: usize) -> Option<u8> {
+        (x < self.width && y < self.height).then(|| self.cells[y * self.width + x])
+    }
+
+    /// The positions next to `(x, y)` horizontally and vertically that lie inside the grid.
+    pub fn neighbours(&self, x: usize, y: usize) -> impl Iterator<Item = (usize, usize)> + '_ {
+        [(0, -1), (1, 0), (0, 1), (-1, 0)]
+            .into_iter()
+            .filter_map(move |(dx, dy)| {
+                let x = x.checked_add_signed(dx)?;
+                let y = y.checked_add_signed(dy)?;
+                self.get(x, y).map(|_| (x, y))
+            })
+    }
+}
+
+impl Solution for Day{{day_padded}} {
+    const DAY: u8 = {{day}};
+
+    type Input = Grid;
+    type PartOne = u32;
+    type PartTwo = u32;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        let width = input.lines().next().map_or(0, str::len);
+        let mut cells = Vec::with_capacity(input.len());
+        for line in input.lines() {
+            if line.len() != width {
+                return Err(ParseError::new(line, format!("expected a row of {width} cells")));
+            }
+            cells.extend_from_slice(line.as_bytes());
+        }
+        Ok(Grid {
+            width,
+            height: input.lines().count(),
+            cells,
+        })
+    }
+
+    fn part_one(grid: &Self::Input) -> Option<u32> {
+        None
+    }
+
+    fn part_two(grid: &Self::Input) -> Option<u32> {
+        None
+    }
+}
+
+fn main() {
+    advent_of_code::solve!(Day{{day_padded}});
+}
+
+advent_of_code::example_tests!(Day{{day_padded}});
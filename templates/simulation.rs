@@ -0,0 +1,55 @@
+//! Day {{day}}: {{title}}
+//! https://adventofcode.com/{{year}}/day/{{day}}
+use advent_of_code::{trace, ParseError, Solution};
+
+pub struct Day{{day_padded}};
+
+/// The state of the simulation, advanced one step at a time.
+#[derive(Debug, Clone)]
+pub struct State {
+    lines: Vec<String>,
+}
+
+impl State {
+    /// Advances the simulation by one step.
+    fn step(&mut self) {}
+}
+
+/// Runs `steps` steps of the simulation on a copy of `state`.
+fn simulate(state: &State, steps: usize) -> State {
+    let mut state = state.clone();
+    for step in 1..=steps {
+        state.step();
+        trace!("after step {step}: {state:?}");
+    }
+    state
+}
+
+impl Solution for Day{{day_padded}} {
+    const DAY: u8 = {{day}};
+
+    type Input = State;
+    type PartOne = u32;
+    type PartTwo = u32;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        Ok(State {
+            lines: input.lines().map(str::to_string).collect(),
+        })
+    }
+
+    fn part_one(state: &Self::Input) -> Option<u32> {
+        let state = simulate(state, 10);
+        None
+    }
+
+    fn part_two(state: &Self::Input) -> Option<u32> {
+        None
+    }
+}
+
+fn main() {
+    advent_of_code::solve!(Day{{day_padded}});
+}
+
+advent_of_code::example_tests!(Day{{day_padded}});
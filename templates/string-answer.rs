@@ -0,0 +1,31 @@
+//! Day {{day}}: {{title}}
+//! https://adventofcode.com/{{year}}/day/{{day}}
+use advent_of_code::{ParseError, Solution};
+
+pub struct Day{{day_padded}};
+
+impl Solution for Day{{day_padded}} {
+    const DAY: u8 = {{day}};
+
+    type Input = Vec<String>;
+    type PartOne = String;
+    type PartTwo = String;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        Ok(input.lines().map(str::to_string).collect())
+    }
+
+    fn part_one(lines: &Self::Input) -> Option<String> {
+        None
+    }
+
+    fn part_two(lines: &Self::Input) -> Option<String> {
+        None
+    }
+}
+
+fn main() {
+    advent_of_code::solve!(Day{{day_padded}});
+}
+
+advent_of_code::example_tests!(Day{{day_padded}});